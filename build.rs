@@ -0,0 +1,30 @@
+//! Compiles `config/cards` into the binary card database embedded in the game, with the parser
+//! and writer of the crate itself.
+
+use std::{
+    env, fs,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+#[allow(dead_code)]
+#[path = "src/data/cards.rs"]
+mod cards;
+
+const CARDS_PATH: &str = "config/cards";
+
+fn main() -> io::Result<()> {
+    println!("cargo::rerun-if-changed={CARDS_PATH}");
+    println!("cargo::rerun-if-changed=src/data/cards.rs");
+
+    let content = fs::read_to_string(CARDS_PATH)?;
+    let cards = cards::parse_cards(&content, cards::CardSet::Base).unwrap_or_else(|errors| {
+        let errors: Vec<String> = errors.iter().map(|e| format!("{CARDS_PATH}:{e}")).collect();
+        panic!("invalid card list:\n{}", errors.join("\n"));
+    });
+
+    let out = Path::new(&env::var("OUT_DIR").expect("OUT_DIR is set by cargo")).join("cards.db");
+    let mut writer = BufWriter::new(fs::File::create(out)?);
+    cards::write_db(&cards, &mut writer)?;
+    writer.flush()
+}
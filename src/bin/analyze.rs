@@ -0,0 +1,64 @@
+use std::{env, process::ExitCode};
+
+use triple_triad::{
+    core::{analysis::Analysis, record::MatchRecord},
+    data::CardDb,
+    error::{Context, Error, Result},
+};
+
+const USAGE: &str = "usage: analyze <record> [--db <cards.db>]";
+
+struct Args {
+    record_path: String,
+    /// The database compiled into the binary when `None`.
+    db_path: Option<String>,
+}
+
+fn parse_args() -> Result<Args> {
+    let usage = || Error::Args(USAGE.to_string());
+    let mut record_path = None;
+    let mut db_path = None;
+
+    let mut iter = env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--db" => db_path = Some(iter.next().ok_or_else(usage)?),
+            _ if record_path.is_none() && !arg.starts_with('-') => record_path = Some(arg),
+            _ => return Err(usage()),
+        }
+    }
+
+    Ok(Args {
+        record_path: record_path.ok_or_else(usage)?,
+        db_path,
+    })
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("ERR: {}", e.report());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<()> {
+    let args = parse_args()?;
+
+    let card_db = match &args.db_path {
+        Some(path) => {
+            CardDb::load(path).with_context(|| format!("loading card database {path}"))?
+        }
+        None => CardDb::embedded().context("loading the embedded card database")?,
+    };
+    let record = MatchRecord::load(&args.record_path)
+        .with_context(|| format!("loading match record {}", args.record_path))?;
+
+    let analysis = Analysis::new(&record, &card_db)
+        .with_context(|| format!("analyzing {}", args.record_path))?;
+    print!("{analysis}");
+
+    Ok(())
+}
@@ -1,29 +1,327 @@
 use std::{
-    fs::File,
-    io::{self, BufRead, BufReader, BufWriter},
-    str::FromStr,
+    collections::BTreeSet,
+    env,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::Path,
+    process::ExitCode,
 };
 
-use triple_triad::data::Card;
+use triple_triad::{
+    core::{card_pools::CardPool, data::CardId},
+    data::{
+        Card, CardDb, CardError, CardQuery, CardSet, parse_cards, parse_cards_toml,
+        write_cards_toml,
+    },
+    locale::{DEFAULT_LOCALE, LocalePack, UI_KEYS},
+    sdl::Font,
+};
+
+const USAGE: &str = "usage: card-db build [--expansion] <in> <out>
+       card-db dump <db>
+       card-db import [--expansion] <in.toml> <out>
+       card-db export <db> <out.toml>
+       card-db validate [--expansion] <in>
+       card-db diff <a> <b>
+       card-db search <db> <query>
+       card-db pools <pools> <db>
+       card-db locale <pack> <db>
+
+<in> is a card list, one `level,name,top,right,bottom,left,element` per line, or a structured file
+of `[[card]]` tables when named `.toml`. It holds the base set, or with --expansion the cards of an
+expansion. diff also reads databases from `.db` files.
+
+<query> lists criteria separated by commas, e.g. `level=1..3,element=Fire,top>=8`: name=<text>,
+level=<n>, level=<min>..<max>, element=<element>, and <side><op><n> with top, right, bottom, left,
+any or sum as side and <, <=, =, >= or > as operator.
+
+<pools> lists NPC card pools, one `name,entry,...` per line, where an entry is a card id or name,
+level=<n> or level=<min>..<max>.
+
+<pack> is a locale pack, one `card.<id> = <name>` or `ui.<key> = <text>` per line.";
+
+const FONT_LAYOUT_PATH: &str = "assets/font.txt";
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<(), String> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let set = match args.iter().position(|&arg| arg == "--expansion") {
+        Some(j) => {
+            args.remove(j);
+            CardSet::Expansion
+        }
+        None => CardSet::Base,
+    };
+
+    match args.as_slice() {
+        ["build", input, output] => build(input, output, set),
+        ["dump", db] => dump(db),
+        ["import", input, output] => import(input, output, set),
+        ["export", db, output] => export(db, output),
+        ["validate", input] => validate(input, set),
+        ["diff", a, b] => diff(a, b),
+        ["search", db, query] => search(db, query),
+        ["pools", pools, db] => pools_check(pools, db),
+        ["locale", pack, db] => locale_check(pack, db),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+/// Compiles the card list `input` into the database `output`, which is left untouched when any
+/// line is invalid.
+fn build(input: &str, output: &str, set: CardSet) -> Result<(), String> {
+    let cards = parse_card_list(input, set)?;
+    write_db(&cards, output)
+}
+
+/// Compiles the structured file `input` into the database `output`.
+fn import(input: &str, output: &str, set: CardSet) -> Result<(), String> {
+    let content = fs::read_to_string(input).map_err(|e| format!("{input}: {e}"))?;
+    let cards = parse_cards_toml(&content, set).map_err(|errors| report(input, &errors))?;
+    write_db(&cards, output)
+}
+
+fn write_db(cards: &[Card], output: &str) -> Result<(), String> {
+    let write = || -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(output)?);
+        CardDb::write(cards, &mut writer)?;
+        writer.flush()
+    };
+    write().map_err(|e| format!("{output}: {e}"))?;
+
+    eprintln!("wrote {} cards to {output}", cards.len());
+
+    Ok(())
+}
+
+/// Prints the database `db` back in the card list format.
+fn dump(db: &str) -> Result<(), String> {
+    let card_db = CardDb::load(db).map_err(|e| format!("{db}: {e}"))?;
+
+    let mut out = io::stdout().lock();
+    for (j, card) in card_db.cards().enumerate() {
+        if !card.fits_list_format() {
+            eprintln!("WARN: card {j} doesn't fit the card list format, use export instead");
+        }
+        writeln!(out, "{card}").map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Decompiles the database `db` into the structured file `output`, without loss.
+fn export(db: &str, output: &str) -> Result<(), String> {
+    let card_db = CardDb::load(db).map_err(|e| format!("{db}: {e}"))?;
+    let cards: Vec<Card> = card_db.cards().collect();
+
+    let write = || -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(output)?);
+        write_cards_toml(&cards, &mut writer)?;
+        writer.flush()
+    };
+    write().map_err(|e| format!("{output}: {e}"))?;
+
+    eprintln!("wrote {} cards to {output}", cards.len());
+
+    Ok(())
+}
+
+fn validate(input: &str, set: CardSet) -> Result<(), String> {
+    let cards = parse_card_list(input, set)?;
+    eprintln!("{input}: {} cards ok", cards.len());
 
-fn main() -> io::Result<()> {
-    // TODO read from args
-    let f_in = File::open("config/cards")?;
-    let reader = BufReader::new(f_in);
+    Ok(())
+}
+
+/// Prints the cards changed, added or removed from `a` to `b`, by index.
+fn diff(a: &str, b: &str) -> Result<(), String> {
+    let a_cards = read_cards(a)?;
+    let b_cards = read_cards(b)?;
+
+    let mut changes = 0;
+    for j in 0..a_cards.len().max(b_cards.len()) {
+        match (a_cards.get(j), b_cards.get(j)) {
+            (Some(a_card), Some(b_card)) if a_card == b_card => continue,
+            (Some(a_card), Some(b_card)) => {
+                println!("~ {j:>3}: {a_card}\n       {b_card}");
+                if a_card.description() != b_card.description() {
+                    println!("       description: {:?}", a_card.description());
+                    println!("                 -> {:?}", b_card.description());
+                }
+            }
+            (Some(a_card), None) => println!("- {j:>3}: {a_card}"),
+            (None, Some(b_card)) => println!("+ {j:>3}: {b_card}"),
+            (None, None) => unreachable!(),
+        }
+        changes += 1;
+    }
 
-    let f_out = File::create("config/cards.db")?;
-    let mut writer = BufWriter::new(f_out);
+    eprintln!("{changes} cards differ");
 
-    for (j, line) in reader.lines().enumerate() {
-        let line = line?;
+    Ok(())
+}
 
-        match Card::from_str(&line) {
-            Ok(card) => card.write_bytes(&mut writer)?,
-            Err(e) => eprintln!("ERR: processing card at line {}: {:?}", j + 1, e),
+/// Prints the cards of `db` matching `query`.
+fn search(db: &str, query: &str) -> Result<(), String> {
+    let card_db = CardDb::load(db).map_err(|e| format!("{db}: {e}"))?;
+    let query: CardQuery = query.parse().map_err(|e| format!("{e}"))?;
+
+    let mut count = 0;
+    for id in card_db.query(&query) {
+        let j = id.index();
+        if let Some(card) = card_db.card(j) {
+            println!("{j:>3}: {card}");
+            count += 1;
         }
     }
 
+    eprintln!("{count} cards found");
+
     Ok(())
 }
 
-// TODO add magic number and header w/card count to file
+/// Resolves every pool of `pools` against `db`, reporting those that can't be drawn from.
+fn pools_check(pools: &str, db: &str) -> Result<(), String> {
+    let card_db = CardDb::load(db).map_err(|e| format!("{db}: {e}"))?;
+    let pools = CardPool::load_all(pools).map_err(|e| format!("{pools}: {e}"))?;
+
+    let mut errors = 0;
+    for pool in &pools {
+        match pool.resolve(&card_db) {
+            Ok(cards) => println!("{}: {} cards", pool.name, cards.0.len()),
+            Err(e) => {
+                eprintln!("ERR: {}: {e}", pool.name);
+                errors += 1;
+            }
+        }
+    }
+
+    match errors {
+        0 => Ok(()),
+        _ => Err(format!("{errors} pools invalid")),
+    }
+}
+
+/// Reports what the locale pack `pack` doesn't translate of `db` and of the UI strings, and the
+/// characters the font has no glyph for. The cards of the default locale are named by `db`.
+fn locale_check(pack: &str, db: &str) -> Result<(), String> {
+    let card_db = CardDb::load(db).map_err(|e| format!("{db}: {e}"))?;
+
+    let path = Path::new(pack);
+    let (dir, code) = match (
+        path.parent(),
+        path.file_name().and_then(|name| name.to_str()),
+    ) {
+        (Some(dir), Some(code)) => (dir, code),
+        _ => return Err(format!("{pack}: invalid locale pack name")),
+    };
+    let locale = LocalePack::load(dir, code).map_err(|e| format!("{pack}: {e}"))?;
+
+    let mut errors = 0;
+    let mut translated = vec![code == DEFAULT_LOCALE; card_db.len()];
+    for (id, _) in &locale.cards {
+        match card_db.resolve(id) {
+            Some(card) => translated[card.index()] = true,
+            None => {
+                eprintln!("ERR: unknown card '{id}'");
+                errors += 1;
+            }
+        }
+    }
+
+    let mut missing = 0;
+    for (j, _) in translated.iter().enumerate().filter(|(_, done)| !**done) {
+        let card = CardId::new(j as u8).expect("card index fits in a card id");
+        println!("missing card.{}", card_db.qualified_name(card));
+        missing += 1;
+    }
+    for key in UI_KEYS
+        .iter()
+        .filter(|key| !locale.strings.contains_key(**key))
+    {
+        println!("missing ui.{key}");
+        missing += 1;
+    }
+    for key in locale
+        .strings
+        .keys()
+        .filter(|key| !UI_KEYS.contains(&key.as_str()))
+    {
+        eprintln!("WARN: unused ui.{key}");
+    }
+
+    // glyphs are looked up per character, whitespace is only spacing
+    let glyphs: BTreeSet<char> = Font::load_layout(FONT_LAYOUT_PATH)
+        .map_err(|e| format!("{FONT_LAYOUT_PATH}: {e}"))?
+        .concat()
+        .into_iter()
+        .collect();
+    let no_glyph: String = locale
+        .cards
+        .iter()
+        .map(|(_, name)| name)
+        .chain(locale.strings.values())
+        .flat_map(|text| text.split('{').enumerate())
+        // skip the `{name}` placeholders
+        .map(|(j, part)| match (j, part.split_once('}')) {
+            (0, _) | (_, None) => part,
+            (_, Some((_, rest))) => rest,
+        })
+        .flat_map(str::chars)
+        .filter(|c| !c.is_whitespace() && !glyphs.contains(c))
+        .collect::<BTreeSet<char>>()
+        .into_iter()
+        .collect();
+    if !no_glyph.is_empty() {
+        eprintln!("WARN: no glyph in {FONT_LAYOUT_PATH} for: {no_glyph}");
+    }
+
+    eprintln!("{missing} missing translations");
+
+    match (errors, missing) {
+        (0, 0) => Ok(()),
+        _ => Err(format!(
+            "{pack}: {errors} errors, {missing} missing translations"
+        )),
+    }
+}
+
+fn read_cards(path: &str) -> Result<Vec<Card>, String> {
+    if path.ends_with(".db") {
+        let card_db = CardDb::load(path).map_err(|e| format!("{path}: {e}"))?;
+        Ok(card_db.cards().collect())
+    } else {
+        // either set may be compared
+        parse_card_list(path, CardSet::Expansion)
+    }
+}
+
+/// Reads the card list or structured file at `path`, reporting every invalid line before failing.
+fn parse_card_list(path: &str, set: CardSet) -> Result<Vec<Card>, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+
+    let cards = if path.ends_with(".toml") {
+        parse_cards_toml(&content, set)
+    } else {
+        parse_cards(&content, set)
+    };
+    cards.map_err(|errors| report(path, &errors))
+}
+
+fn report(path: &str, errors: &[CardError]) -> String {
+    for e in errors {
+        eprintln!("ERR: {path}:{e}");
+    }
+    format!("{path}: {} errors", errors.len())
+}
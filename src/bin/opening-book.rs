@@ -0,0 +1,127 @@
+use std::{env, io, str::FromStr};
+
+use triple_triad::{
+    core::{
+        ai::{SOLVE_DEPTH, Search, Strength, Weights, available_threads},
+        battle::{BattleSetup, Pool},
+        book::OpeningBook,
+        card_pools::CardPool,
+        game::Game,
+        npc::{NPCS, Npc},
+    },
+    data::CardDb,
+    profile::Profile,
+    sys::rand::Rng,
+};
+
+const POOLS_PATH: &str = "config/pools";
+
+const USAGE: &str = "usage: opening-book [--deals <n>] [--npc <name>]... [--depth <n>] \
+                     [--seed <n>] [--threads <n>] [--out <path>] [--db <cards.db>]";
+
+struct Args {
+    deals: usize,
+    npcs: Vec<&'static Npc>,
+    depth: u8,
+    seed: u64,
+    threads: usize,
+    out: String,
+    /// The database compiled into the binary when `None`.
+    db_path: Option<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        deals: 16,
+        npcs: Vec::new(),
+        depth: SOLVE_DEPTH,
+        seed: 0,
+        threads: available_threads(),
+        out: "config/openings.book".to_string(),
+        db_path: None,
+    };
+
+    let mut iter = env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or(USAGE);
+
+        match arg.as_str() {
+            "--deals" => args.deals = parse_number(&value()?)?,
+            "--npc" => {
+                let name = value()?;
+                let npc = Npc::find(&name).ok_or_else(|| format!("unknown npc '{name}'"))?;
+                args.npcs.push(npc);
+            }
+            "--depth" => args.depth = parse_number(&value()?)?,
+            "--seed" => args.seed = parse_number(&value()?)?,
+            "--threads" => args.threads = parse_number(&value()?)?,
+            "--out" => args.out = value()?,
+            "--db" => args.db_path = Some(value()?),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    // by default, the NPCs playing deep enough to use the book
+    if args.npcs.is_empty() {
+        args.npcs = NPCS
+            .iter()
+            .filter(|npc| matches!(npc.strength, Strength::Lookahead(depth) if depth >= args.depth))
+            .collect();
+    }
+
+    Ok(args)
+}
+
+fn parse_number<T: FromStr>(s: &str) -> Result<T, String> {
+    s.parse().map_err(|_| format!("invalid number '{s}'"))
+}
+
+/// Solves the first two placements of deals like those of the game: the player's hand drawn from
+/// the starter cards of a new profile and the opponent's from each NPC pool, under the NPC rules.
+///
+/// `config/openings.book` is built by `opening-book --threads 1`, single threaded so that the
+/// searches, and the book, are reproducible.
+fn main() -> io::Result<()> {
+    let args = parse_args().map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let card_db = match &args.db_path {
+        Some(path) => CardDb::load(path)?,
+        None => CardDb::embedded()?,
+    };
+    let pools = CardPool::load_all(POOLS_PATH)?;
+    let player_pool: Pool = Profile::new(&card_db).inventory.iter_distinct().collect();
+    let mut book = OpeningBook::new(args.depth, &card_db);
+    let mut rng = Rng::from_seed(args.seed);
+
+    for npc in &args.npcs {
+        let pool = CardPool::resolve_named(&pools, npc.pool, &card_db).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("pool '{}': {e}", npc.pool),
+            )
+        })?;
+        let search =
+            Search::new(npc.rules, &card_db, Weights::default()).with_threads(args.threads);
+
+        for deal in 0..args.deals {
+            let setup = BattleSetup {
+                p1_hand: player_pool.draw_hand(&mut rng),
+                p2_hand: pool.draw_hand(&mut rng),
+            };
+            book.add_deal(&Game::new(&setup), npc.rules, &search);
+
+            eprintln!(
+                "{} deal {}/{}: {} entries",
+                npc.name,
+                deal + 1,
+                args.deals,
+                book.len()
+            );
+        }
+    }
+
+    book.save(&args.out)?;
+    eprintln!("saved {} entries to {}", book.len(), args.out);
+
+    Ok(())
+}
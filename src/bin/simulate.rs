@@ -0,0 +1,289 @@
+use std::{
+    env,
+    io::{self, BufWriter, Write},
+};
+
+use triple_triad::{
+    core::{
+        ai::{Weights, available_threads},
+        battle::{HAND_SIZE, Pool},
+        card_pools::CardPool,
+        data::CardId,
+        npc::{NPCS, Npc},
+        personality::Personality,
+        rules::RuleSet,
+        sim::{Deck, Seat, Simulation, Tally},
+    },
+    data::CardDb,
+    sys::rand::Rng,
+};
+
+const PERSONALITIES_PATH: &str = "config/personalities";
+
+const POOLS_PATH: &str = "config/pools";
+
+const USAGE: &str = "usage: simulate [--matches <n>] [--seed <n>] [--rules <rule,...>] \
+                     [--p1 <npc>] [--p2 <npc>] [--p1-hand <id,...>] [--p2-hand <id,...>] \
+                     [--threads <n>] [--format csv|json] [--db <cards.db>]";
+
+#[derive(Clone, Copy)]
+enum Format {
+    Csv,
+    Json,
+}
+
+struct Args {
+    matches: usize,
+    seed: Option<u64>,
+    rules: Option<RuleSet>,
+    npcs: [&'static Npc; 2],
+    hands: [Option<[CardId; HAND_SIZE]>; 2],
+    threads: usize,
+    format: Format,
+    /// The database compiled into the binary when `None`.
+    db_path: Option<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        matches: 100,
+        seed: None,
+        rules: None,
+        npcs: [&NPCS[3], &NPCS[3]],
+        hands: [None, None],
+        threads: available_threads(),
+        format: Format::Csv,
+        db_path: None,
+    };
+
+    let mut iter = env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or(USAGE);
+
+        match arg.as_str() {
+            "--matches" => args.matches = parse_number(&value()?)?,
+            "--seed" => args.seed = Some(parse_number(&value()?)?),
+            "--rules" => args.rules = Some(value()?.parse().map_err(|e| format!("{e}"))?),
+            "--p1" => args.npcs[0] = find_npc(&value()?)?,
+            "--p2" => args.npcs[1] = find_npc(&value()?)?,
+            "--p1-hand" => args.hands[0] = Some(parse_hand(&value()?)?),
+            "--p2-hand" => args.hands[1] = Some(parse_hand(&value()?)?),
+            "--threads" => args.threads = parse_number(&value()?)?,
+            "--format" => {
+                args.format = match value()?.as_str() {
+                    "csv" => Format::Csv,
+                    "json" => Format::Json,
+                    format => return Err(format!("unknown format '{format}'")),
+                }
+            }
+            "--db" => args.db_path = Some(value()?),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    Ok(args)
+}
+
+fn parse_number<T: std::str::FromStr>(s: &str) -> Result<T, String> {
+    s.parse().map_err(|_| format!("invalid number '{s}'"))
+}
+
+fn find_npc(name: &str) -> Result<&'static Npc, String> {
+    Npc::find(name).ok_or_else(|| {
+        let names: Vec<&str> = NPCS.iter().map(|npc| npc.name).collect();
+        format!(
+            "unknown npc '{name}', expected one of: {}",
+            names.join(", ")
+        )
+    })
+}
+
+fn parse_hand(s: &str) -> Result<[CardId; HAND_SIZE], String> {
+    let cards: Vec<CardId> = s
+        .split(',')
+        .map(|id| id.trim().parse().ok().and_then(CardId::new))
+        .collect::<Option<_>>()
+        .ok_or_else(|| format!("invalid card id in '{s}'"))?;
+
+    cards
+        .try_into()
+        .map_err(|_| format!("a hand has exactly {HAND_SIZE} cards: '{s}'"))
+}
+
+fn main() -> Result<(), String> {
+    let args = parse_args()?;
+    let rules = args.rules.unwrap_or(args.npcs[1].rules);
+    let seed = args.seed.unwrap_or_else(|| Rng::init().u64());
+
+    let card_db = match &args.db_path {
+        Some(path) => CardDb::load(path),
+        None => CardDb::embedded(),
+    }
+    .map_err(|e| e.to_string())?;
+    let personalities = Personality::load_all(PERSONALITIES_PATH).map_err(|e| e.to_string())?;
+    let pools = CardPool::load_all(POOLS_PATH).map_err(|e| e.to_string())?;
+
+    let mut npc_pools: Vec<Pool> = Vec::new();
+    for npc in args.npcs {
+        let pool = CardPool::resolve_named(&pools, npc.pool, &card_db)
+            .map_err(|e| format!("pool '{}': {e}", npc.pool))?;
+        npc_pools.push(pool);
+    }
+
+    let mut weights = [Weights::default(); 2];
+    for (weights, npc) in weights.iter_mut().zip(args.npcs) {
+        *weights = Personality::find(&personalities, npc.personality)
+            .ok_or_else(|| format!("unknown personality '{}'", npc.personality))?
+            .weights;
+    }
+
+    let seats = [0, 1].map(|seat| Seat {
+        strength: args.npcs[seat].strength,
+        weights: weights[seat],
+        deck: match args.hands[seat] {
+            Some(hand) => Deck::Fixed(hand),
+            None => Deck::Pool(&npc_pools[seat]),
+        },
+    });
+
+    eprintln!("seed {seed}");
+
+    let outcomes = Simulation::new(rules, &card_db, seats)
+        .with_threads(args.threads)
+        .run(args.matches, seed);
+    let tally = Tally::new(&outcomes, &card_db);
+
+    let names = args.npcs.map(|npc| npc.name);
+    let mut writer = BufWriter::new(io::stdout().lock());
+    match args.format {
+        Format::Csv => write_csv(&mut writer, &tally, names, &card_db),
+        Format::Json => write_json(&mut writer, &tally, names, &card_db),
+    }
+    .and_then(|()| writer.flush())
+    .map_err(|e| e.to_string())
+}
+
+/// Writes the summary as `metric,value` rows, then a blank line and the per card table.
+fn write_csv(
+    w: &mut impl Write,
+    tally: &Tally,
+    names: [&str; 2],
+    card_db: &CardDb,
+) -> io::Result<()> {
+    writeln!(w, "metric,value")?;
+    writeln!(w, "matches,{}", tally.matches)?;
+    writeln!(w, "draws,{}", tally.draws)?;
+    writeln!(w, "draw_rate,{:.4}", tally.draw_rate())?;
+    for (seat, name) in names.iter().enumerate() {
+        let metric = |metric: &str| escape_csv(&format!("p{}_{name}_{metric}", seat + 1));
+        writeln!(w, "{},{}", metric("wins"), tally.wins[seat])?;
+        writeln!(w, "{},{:.4}", metric("win_rate"), tally.win_rate(seat))?;
+    }
+    writeln!(
+        w,
+        "first_player_advantage,{:.4}",
+        tally.first_player_advantage()
+    )?;
+
+    writeln!(w)?;
+    writeln!(w, "id,name,matches,wins,draws,losses,score,contribution")?;
+    for (j, card) in tally.cards.iter().enumerate() {
+        if card.matches == 0 {
+            continue;
+        }
+        writeln!(
+            w,
+            "{j},{},{},{},{},{},{:.4},{:.4}",
+            escape_csv(&card_db.names[j]),
+            card.matches,
+            card.wins,
+            card.draws,
+            card.losses(),
+            card.score(),
+            card.contribution()
+        )?;
+    }
+
+    Ok(())
+}
+
+fn write_json(
+    w: &mut impl Write,
+    tally: &Tally,
+    names: [&str; 2],
+    card_db: &CardDb,
+) -> io::Result<()> {
+    writeln!(w, "{{")?;
+    writeln!(w, "  \"matches\": {},", tally.matches)?;
+    writeln!(w, "  \"draws\": {},", tally.draws)?;
+    writeln!(w, "  \"draw_rate\": {:.4},", tally.draw_rate())?;
+    writeln!(
+        w,
+        "  \"first_player_advantage\": {:.4},",
+        tally.first_player_advantage()
+    )?;
+
+    writeln!(w, "  \"seats\": [")?;
+    for (seat, name) in names.iter().enumerate() {
+        let comma = if seat == 0 { "," } else { "" };
+        writeln!(
+            w,
+            "    {{ \"npc\": \"{}\", \"wins\": {}, \"win_rate\": {:.4} }}{comma}",
+            escape_json(name),
+            tally.wins[seat],
+            tally.win_rate(seat)
+        )?;
+    }
+    writeln!(w, "  ],")?;
+
+    writeln!(w, "  \"cards\": [")?;
+    let mut cards = tally
+        .cards
+        .iter()
+        .enumerate()
+        .filter(|(_, card)| card.matches > 0)
+        .peekable();
+    while let Some((j, card)) = cards.next() {
+        let comma = if cards.peek().is_some() { "," } else { "" };
+        writeln!(
+            w,
+            "    {{ \"id\": {j}, \"name\": \"{}\", \"matches\": {}, \"wins\": {}, \"draws\": {}, \
+             \"losses\": {}, \"score\": {:.4}, \"contribution\": {:.4} }}{comma}",
+            escape_json(&card_db.names[j]),
+            card.matches,
+            card.wins,
+            card.draws,
+            card.losses(),
+            card.score(),
+            card.contribution()
+        )?;
+    }
+    writeln!(w, "  ]")?;
+    writeln!(w, "}}")
+}
+
+/// Escapes `s` to be put between the quotes of a JSON string.
+fn escape_json(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Quotes `s` as a CSV field if it holds a separator, a quote or a line break.
+fn escape_csv(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
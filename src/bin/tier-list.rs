@@ -0,0 +1,196 @@
+use std::{
+    env,
+    io::{self, BufWriter, Write},
+    str::FromStr,
+};
+
+use triple_triad::{
+    core::{
+        ai::{Weights, available_threads},
+        battle::HAND_SIZE,
+        npc::{NPCS, Npc},
+        personality::Personality,
+        rules::RuleSet,
+        sim::{Deck, Seat, Simulation, Tally},
+        tier::TierList,
+    },
+    data::{CardDb, CardQuery},
+    sys::rand::Rng,
+};
+
+const PERSONALITIES_PATH: &str = "config/personalities";
+
+const USAGE: &str = "usage: tier-list [--matches <n>] [--seed <n>] [--rules <rule,...>] \
+                     [--npc <name>] [--threads <n>] [--format md|csv] [--db <cards.db>]";
+
+#[derive(Clone, Copy)]
+enum Format {
+    Markdown,
+    Csv,
+}
+
+struct Args {
+    matches: usize,
+    seed: Option<u64>,
+    rules: RuleSet,
+    npc: &'static Npc,
+    threads: usize,
+    format: Format,
+    /// The database compiled into the binary when `None`.
+    db_path: Option<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        matches: 200,
+        seed: None,
+        rules: RuleSet::default(),
+        npc: &NPCS[3],
+        threads: available_threads(),
+        format: Format::Markdown,
+        db_path: None,
+    };
+
+    let mut iter = env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or(USAGE);
+
+        match arg.as_str() {
+            "--matches" => args.matches = parse_number(&value()?)?,
+            "--seed" => args.seed = Some(parse_number(&value()?)?),
+            "--rules" => args.rules = value()?.parse().map_err(|e| format!("{e}"))?,
+            "--npc" => {
+                let name = value()?;
+                args.npc = Npc::find(&name).ok_or_else(|| format!("unknown npc '{name}'"))?;
+            }
+            "--threads" => args.threads = parse_number(&value()?)?,
+            "--format" => {
+                args.format = match value()?.as_str() {
+                    "md" => Format::Markdown,
+                    "csv" => Format::Csv,
+                    format => return Err(format!("unknown format '{format}'")),
+                }
+            }
+            "--db" => args.db_path = Some(value()?),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    Ok(args)
+}
+
+fn parse_number<T: FromStr>(s: &str) -> Result<T, String> {
+    s.parse().map_err(|_| format!("invalid number '{s}'"))
+}
+
+fn main() -> Result<(), String> {
+    let args = parse_args()?;
+    let seed = args.seed.unwrap_or_else(|| Rng::init().u64());
+
+    let card_db = match &args.db_path {
+        Some(path) => CardDb::load(path),
+        None => CardDb::embedded(),
+    }
+    .map_err(|e| e.to_string())?;
+    let personalities = Personality::load_all(PERSONALITIES_PATH).map_err(|e| e.to_string())?;
+    let weights: Weights = Personality::find(&personalities, args.npc.personality)
+        .ok_or_else(|| format!("unknown personality '{}'", args.npc.personality))?
+        .weights;
+
+    eprintln!("seed {seed}");
+
+    let mut distinct = card_db.levels.clone();
+    distinct.sort_unstable();
+    distinct.dedup();
+
+    let mut tier_lists = Vec::new();
+    for level in distinct {
+        let pool = card_db.pool(&CardQuery::new().with_levels(level..=level));
+        if pool.0.len() <= HAND_SIZE {
+            eprintln!("skipping level {level}: not enough cards to draw hands from");
+            continue;
+        }
+
+        // both seats play the same way with hands drawn from the band
+        let seats = [0, 1].map(|_| Seat {
+            strength: args.npc.strength,
+            weights,
+            deck: Deck::Pool(&pool),
+        });
+
+        let outcomes = Simulation::new(args.rules, &card_db, seats)
+            .with_threads(args.threads)
+            .run(args.matches, seed);
+        let tally = Tally::new(&outcomes, &card_db);
+
+        tier_lists.push(TierList::new(level, &pool.0, &tally));
+    }
+
+    let mut writer = BufWriter::new(io::stdout().lock());
+    match args.format {
+        Format::Markdown => write_markdown(&mut writer, &tier_lists, &card_db),
+        Format::Csv => write_csv(&mut writer, &tier_lists, &card_db),
+    }
+    .and_then(|()| writer.flush())
+    .map_err(|e| e.to_string())
+}
+
+fn write_markdown(w: &mut impl Write, tier_lists: &[TierList], card_db: &CardDb) -> io::Result<()> {
+    for (j, tier_list) in tier_lists.iter().enumerate() {
+        if j > 0 {
+            writeln!(w)?;
+        }
+
+        writeln!(w, "## Level {}", tier_list.level)?;
+        writeln!(w)?;
+        writeln!(
+            w,
+            "| rank | tier | id | name | matches | win delta | captures | survival |"
+        )?;
+        writeln!(w, "|---:|:---:|---:|:---|---:|---:|---:|---:|")?;
+
+        for (rank, entry) in tier_list.entries.iter().enumerate() {
+            writeln!(
+                w,
+                "| {} | {} | {} | {} | {} | {:+.3} | {:.2} | {:.1}% |",
+                rank + 1,
+                entry.tier,
+                entry.card.index(),
+                card_db.names[entry.card.index()],
+                entry.matches,
+                entry.win_delta,
+                entry.capture_rate,
+                entry.survival_rate * 100.0
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_csv(w: &mut impl Write, tier_lists: &[TierList], card_db: &CardDb) -> io::Result<()> {
+    writeln!(
+        w,
+        "level,rank,tier,id,name,matches,win_delta,capture_rate,survival_rate"
+    )?;
+
+    for tier_list in tier_lists {
+        for (rank, entry) in tier_list.entries.iter().enumerate() {
+            writeln!(
+                w,
+                "{},{},{},{},{},{},{:.4},{:.4},{:.4}",
+                tier_list.level,
+                rank + 1,
+                entry.tier,
+                entry.card.index(),
+                card_db.names[entry.card.index()],
+                entry.matches,
+                entry.win_delta,
+                entry.capture_rate,
+                entry.survival_rate
+            )?;
+        }
+    }
+
+    Ok(())
+}
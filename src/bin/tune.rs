@@ -0,0 +1,189 @@
+use std::{env, str::FromStr};
+
+use triple_triad::{
+    core::{
+        ai::{Strength, available_threads},
+        battle::HAND_SIZE,
+        card_pools::CardPool,
+        npc::{NPCS, Npc},
+        personality::Personality,
+        rules::RuleSet,
+        tuning::Tuner,
+    },
+    data::{CardDb, CardQuery},
+    sys::rand::Rng,
+};
+
+const PERSONALITIES_PATH: &str = "config/personalities";
+
+const POOLS_PATH: &str = "config/pools";
+
+const USAGE: &str = "usage: tune [--iterations <n>] [--matches <n>] [--seed <n>] \
+                     [--rules <rule,...>] [--depth <n>] [--pool <npc>] [--cards <query>] \
+                     [--baseline <personality>] [--start <personality>] [--step <n>] \
+                     [--name <personality>] [--out <path>] [--threads <n>] [--db <cards.db>]";
+
+struct Args {
+    iterations: usize,
+    matches: usize,
+    seed: Option<u64>,
+    rules: RuleSet,
+    depth: u8,
+    pool: &'static Npc,
+    /// Cards to draw hands from instead of the NPC pool.
+    cards: Option<CardQuery>,
+    baseline: String,
+    start: Option<String>,
+    step: i32,
+    name: String,
+    out: String,
+    threads: usize,
+    /// The database compiled into the binary when `None`.
+    db_path: Option<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        iterations: 50,
+        matches: 200,
+        seed: None,
+        rules: RuleSet::default(),
+        depth: 2,
+        pool: &NPCS[4],
+        cards: None,
+        baseline: "balanced".to_string(),
+        start: None,
+        step: 20,
+        name: "tuned".to_string(),
+        out: PERSONALITIES_PATH.to_string(),
+        threads: available_threads(),
+        db_path: None,
+    };
+
+    let mut iter = env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or(USAGE);
+
+        match arg.as_str() {
+            "--iterations" => args.iterations = parse_number(&value()?)?,
+            "--matches" => args.matches = parse_number(&value()?)?,
+            "--seed" => args.seed = Some(parse_number(&value()?)?),
+            "--rules" => args.rules = value()?.parse().map_err(|e| format!("{e}"))?,
+            "--depth" => args.depth = parse_number(&value()?)?,
+            "--pool" => {
+                let name = value()?;
+                args.pool = Npc::find(&name).ok_or_else(|| format!("unknown npc '{name}'"))?;
+            }
+            "--cards" => args.cards = Some(value()?.parse().map_err(|e| format!("{e}"))?),
+            "--baseline" => args.baseline = value()?,
+            "--start" => args.start = Some(value()?),
+            "--step" => args.step = parse_number(&value()?)?,
+            "--name" => args.name = value()?,
+            "--out" => args.out = value()?,
+            "--threads" => args.threads = parse_number(&value()?)?,
+            "--db" => args.db_path = Some(value()?),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    Ok(args)
+}
+
+fn parse_number<T: FromStr>(s: &str) -> Result<T, String> {
+    s.parse().map_err(|_| format!("invalid number '{s}'"))
+}
+
+fn main() -> Result<(), String> {
+    let args = parse_args()?;
+    let seed = args.seed.unwrap_or_else(|| Rng::init().u64());
+
+    let card_db = match &args.db_path {
+        Some(path) => CardDb::load(path),
+        None => CardDb::embedded(),
+    }
+    .map_err(|e| e.to_string())?;
+    let personalities = Personality::load_all(PERSONALITIES_PATH).map_err(|e| e.to_string())?;
+
+    let find = |name: &str| {
+        Personality::find(&personalities, name)
+            .map(|personality| personality.weights)
+            .ok_or_else(|| format!("unknown personality '{name}'"))
+    };
+    let baseline = find(&args.baseline)?;
+    let start = match &args.start {
+        Some(name) => find(name)?,
+        None => baseline,
+    };
+
+    let pool = match &args.cards {
+        Some(query) => {
+            let pool = card_db.pool(query);
+            if pool.0.len() <= HAND_SIZE {
+                return Err(format!(
+                    "{} cards match, not enough to draw hands from",
+                    pool.0.len()
+                ));
+            }
+            pool
+        }
+        None => {
+            let pools = CardPool::load_all(POOLS_PATH).map_err(|e| e.to_string())?;
+            CardPool::resolve_named(&pools, args.pool.pool, &card_db)
+                .map_err(|e| format!("pool '{}': {e}", args.pool.pool))?
+        }
+    };
+
+    eprintln!("seed {seed}");
+
+    let mut tuner = Tuner::new(
+        args.rules,
+        &card_db,
+        &pool,
+        Strength::Lookahead(args.depth),
+        seed,
+    )
+    .with_baseline(baseline)
+    .with_start(start)
+    .with_matches(args.matches)
+    .with_step(args.step)
+    .with_threads(args.threads);
+
+    for j in 0..args.iterations {
+        let step = tuner.step();
+        let (best, best_score) = tuner.best;
+        eprintln!(
+            "{:>4} {:?} {:.4}{} best {:?} {:.4}",
+            j + 1,
+            step.candidate,
+            step.score,
+            if step.accepted { " *" } else { "" },
+            best,
+            best_score
+        );
+    }
+
+    let (weights, score) = tuner.best;
+    let tuned = Personality {
+        name: args.name.clone(),
+        weights,
+    };
+    println!("{tuned} scores {score:.4} against {}", args.baseline);
+
+    // the command reproducing the run, to be kept along with the weights
+    let mut command: Vec<String> = env::args().skip(1).collect();
+    if args.seed.is_none() {
+        command.extend(["--seed".to_string(), seed.to_string()]);
+    }
+    let comment = format!(
+        "{}: tune {}\nscores {score:.4} against {}",
+        args.name,
+        command.join(" "),
+        args.baseline
+    );
+
+    // the NPCs naming the preset play with the new weights from then on
+    Personality::save(&args.out, &comment, &tuned).map_err(|e| e.to_string())?;
+    eprintln!("saved {} to {}", args.name, args.out);
+
+    Ok(())
+}
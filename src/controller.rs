@@ -0,0 +1,164 @@
+use std::{
+    collections::VecDeque,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::{core::ai::Agent, core::battle::Player, event::Command, net::Peer};
+
+// ======================================== Controller =============================================
+
+/// Who plays the turns of a seat.
+pub enum Controller {
+    /// The local keyboard.
+    Human,
+    Ai(Agent),
+    /// A remote player, whose commands are received from the peer.
+    Network(Peer),
+    /// A list of commands played back, one per frame.
+    Script(Script),
+}
+
+/// Controller named on the command line: `human`, `ai`, `script:<path>`, `listen:<addr>` or
+/// `connect:<addr>`.
+#[derive(Clone, Debug)]
+pub enum ControllerSpec {
+    Human,
+    Ai,
+    Script(PathBuf),
+    /// Hosts the match, waiting for the peer to connect.
+    Listen(String),
+    /// Joins a match hosted by the peer, which sends the rules and hands.
+    Connect(String),
+}
+
+impl FromStr for ControllerSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let spec = match s.split_once(':') {
+            None if s == "human" => ControllerSpec::Human,
+            None if s == "ai" => ControllerSpec::Ai,
+            Some(("script", path)) => ControllerSpec::Script(path.into()),
+            Some(("listen", addr)) => ControllerSpec::Listen(addr.to_string()),
+            Some(("connect", addr)) => ControllerSpec::Connect(addr.to_string()),
+            _ => return Err(format!("invalid controller '{s}'")),
+        };
+
+        Ok(spec)
+    }
+}
+
+impl fmt::Display for ControllerSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ControllerSpec::Human => write!(f, "human"),
+            ControllerSpec::Ai => write!(f, "ai"),
+            ControllerSpec::Script(path) => write!(f, "script:{}", path.display()),
+            ControllerSpec::Listen(addr) => write!(f, "listen:{addr}"),
+            ControllerSpec::Connect(addr) => write!(f, "connect:{addr}"),
+        }
+    }
+}
+
+// =========================================== Seats ===============================================
+
+/// The controllers of both players.
+pub struct Seats {
+    pub p1: Controller,
+    pub p2: Controller,
+}
+
+impl Seats {
+    pub fn get(&self, player: Player) -> &Controller {
+        match player {
+            Player::P1 => &self.p1,
+            Player::P2 => &self.p2,
+        }
+    }
+
+    pub fn get_mut(&mut self, player: Player) -> &mut Controller {
+        match player {
+            Player::P1 => &mut self.p1,
+            Player::P2 => &mut self.p2,
+        }
+    }
+
+    pub fn is_human(&self, player: Player) -> bool {
+        matches!(self.get(player), Controller::Human)
+    }
+
+    /// Returns the AI seat currently searching for a move, with the time spent so far.
+    pub fn thinking(&self) -> Option<(Player, Duration)> {
+        [Player::P1, Player::P2]
+            .into_iter()
+            .find_map(|player| match self.get(player) {
+                Controller::Ai(agent) => Some((player, agent.thinking()?)),
+                _ => None,
+            })
+    }
+}
+
+// =========================================== Script ==============================================
+
+/// Commands read from a file, one per line in their text form. Blank lines and lines starting with
+/// `#` are skipped.
+#[derive(Clone, Debug, Default)]
+pub struct Script {
+    commands: VecDeque<Command>,
+}
+
+impl Script {
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        content
+            .parse()
+            .map_err(|e: ScriptError| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+    }
+
+    /// Returns the next command, or `None` once the script is over.
+    pub fn next_command(&mut self) -> Option<Command> {
+        self.commands.pop_front()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+}
+
+impl FromStr for Script {
+    type Err = ScriptError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut commands = VecDeque::new();
+
+        for (j, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let command = line.parse().map_err(|e| ScriptError {
+                line: j + 1,
+                message: format!("{e}"),
+            })?;
+            commands.push_back(command);
+        }
+
+        Ok(Self { commands })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ScriptError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
@@ -0,0 +1,485 @@
+use std::{
+    num::NonZero,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicI32, AtomicUsize, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    core::{
+        battle::{BOARD_SIZE, BoardCoords, Direction, Player},
+        book::OpeningBook,
+        game::{Game, Move},
+        rules::{RuleSet, side},
+        table::{Bound, Entry, TranspositionTable, Zobrist},
+        worker::{AiWorker, Request},
+    },
+    data::CardDb,
+    sys::rand::Rng,
+};
+
+/// Search depth that reaches the end of any match.
+pub const SOLVE_DEPTH: u8 = (BOARD_SIZE * BOARD_SIZE) as u8;
+
+// ========================================= Strength ==============================================
+
+/// How hard an AI controlled player tries.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Strength {
+    /// Plays any legal move.
+    Random,
+    /// Searches `depth` plies ahead, a depth of 1 being a greedy player.
+    Lookahead(u8),
+}
+
+impl Strength {
+    pub const PERFECT: Self = Self::Lookahead(SOLVE_DEPTH);
+}
+
+// ========================================== Weights ==============================================
+
+/// Positional evaluation terms, in hundredths of a card. All zero evaluates material alone.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Weights {
+    /// Bonus per corner owned.
+    pub corner: i32,
+    /// Penalty per weak side facing an empty cell.
+    pub exposed: i32,
+    /// Bonus per empty cell bordered by two or more opponent cards, with Same or Plus in play.
+    pub combo: i32,
+}
+
+impl Weights {
+    /// Evaluation of a single card of material.
+    pub const CARD: i32 = 100;
+
+    /// Sides up to this value are considered weak.
+    pub const WEAK_SIDE: u8 = 4;
+}
+
+// =========================================== Agent ===============================================
+
+/// AI controlled seat.
+///
+/// Moves are computed by a background worker within `budget`, so the game keeps running while the
+/// AI thinks.
+pub struct Agent {
+    pub player: Player,
+    pub strength: Strength,
+    pub weights: Weights,
+    /// Time allowed to compute a move.
+    pub budget: Duration,
+    pub book: Option<Arc<OpeningBook>>,
+    /// Move being carried out, it spans both the card selection and placement phases.
+    pub plan: Option<Move>,
+    thinking: Option<Instant>,
+    worker: AiWorker,
+}
+
+impl Agent {
+    pub const DEFAULT_BUDGET: Duration = Duration::from_secs(2);
+
+    pub fn new(player: Player, strength: Strength, weights: Weights, card_db: Arc<CardDb>) -> Self {
+        Self {
+            player,
+            strength,
+            weights,
+            budget: Self::DEFAULT_BUDGET,
+            book: None,
+            plan: None,
+            thinking: None,
+            worker: AiWorker::spawn(card_db),
+        }
+    }
+
+    pub fn with_budget(mut self, budget: Duration) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    pub fn with_book(mut self, book: Option<Arc<OpeningBook>>) -> Self {
+        self.book = book;
+        self
+    }
+
+    /// Starts computing a move for `game` in the background.
+    pub fn think(&mut self, game: Game, rules: RuleSet, seed: u64) {
+        self.worker.request(Request {
+            game,
+            rules,
+            strength: self.strength,
+            weights: self.weights,
+            budget: self.budget,
+            book: self.book.clone(),
+            seed,
+        });
+        self.thinking = Some(Instant::now());
+    }
+
+    /// Returns the computed move once the worker is done, `Some(None)` meaning no legal move.
+    pub fn poll(&mut self) -> Option<Option<Move>> {
+        self.thinking?;
+
+        let reply = self.worker.poll();
+        if reply.is_some() {
+            self.thinking = None;
+        }
+
+        reply
+    }
+
+    /// Returns how long the agent has been thinking, if it is.
+    pub fn thinking(&self) -> Option<Duration> {
+        self.thinking.map(|since| since.elapsed())
+    }
+}
+
+// ========================================== Search ===============================================
+
+/// Negamax search with alpha-beta pruning and a transposition table.
+///
+/// With more than one thread the root moves are shared among workers, which prune against a
+/// common alpha and share the table. A single thread searches the moves in order, so results are
+/// reproducible. The table persists across searches as long as the rules and weights are the same.
+///
+/// Searches at least as deep as the opening book's play its moves, when it has one for the
+/// position.
+///
+/// A search past its deadline, or cancelled, is aborted and yields no result, as does any later
+/// search until `set_deadline` is called. `best_move` then falls back to the deepest search
+/// completed in time.
+///
+/// The AI sees both hands regardless of `RuleSet::open`.
+pub struct Search<'a> {
+    pub rules: RuleSet,
+    pub card_db: &'a CardDb,
+    pub weights: Weights,
+    pub threads: usize,
+    pub book: Option<Arc<OpeningBook>>,
+    deadline: Option<Instant>,
+    cancel: Option<Arc<AtomicBool>>,
+    aborted: AtomicBool,
+    table: TranspositionTable,
+    zobrist: Zobrist,
+}
+
+impl<'a> Search<'a> {
+    const TABLE_MIN_DEPTH: u8 = 3;
+
+    pub fn new(rules: RuleSet, card_db: &'a CardDb, weights: Weights) -> Self {
+        Self {
+            rules,
+            card_db,
+            weights,
+            threads: 1,
+            book: None,
+            deadline: None,
+            cancel: None,
+            aborted: AtomicBool::new(false),
+            table: TranspositionTable::default(),
+            zobrist: Zobrist::new(),
+        }
+    }
+
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    pub fn with_book(mut self, book: Arc<OpeningBook>) -> Self {
+        self.book = Some(book);
+        self
+    }
+
+    /// Aborts searches running past `deadline`.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.set_deadline(deadline);
+        self
+    }
+
+    /// Aborts searches running past `deadline`, lifting the abort of earlier searches so that the
+    /// next one, and the table it inherits, can be used again.
+    pub fn set_deadline(&mut self, deadline: Instant) {
+        self.deadline = Some(deadline);
+        self.aborted.store(false, Ordering::Relaxed);
+    }
+
+    /// Aborts the search as soon as `cancel` is set.
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Forgets the positions searched so far.
+    pub fn clear_table(&self) {
+        self.table.clear();
+    }
+
+    /// Returns the move to play in `game` for the player on turn, or `None` if there is none.
+    pub fn best_move(&self, game: &Game, strength: Strength, rng: &mut Rng) -> Option<Move> {
+        if let Strength::Lookahead(depth) = strength
+            && let Some(book) = &self.book
+            && depth >= book.depth
+            && let Some(mv) = book.get(game, self.rules)
+        {
+            return Some(mv);
+        }
+
+        match strength {
+            Strength::Random => {
+                let moves: Vec<Move> = game.moves().collect();
+                if moves.is_empty() {
+                    return None;
+                }
+                let j = rng.u8_in(0..moves.len() as u8) as usize;
+                Some(moves[j])
+            }
+
+            Strength::Lookahead(depth) if self.deadline.is_some() => {
+                // iterative deepening, keeping the deepest result found in time
+                let mut best = None;
+                for depth in 1..=depth {
+                    match self.search(game, depth) {
+                        Some((mv, _)) => best = Some(mv),
+                        None => break,
+                    }
+                }
+
+                best.or_else(|| game.moves().next())
+            }
+
+            Strength::Lookahead(depth) => self.search(game, depth).map(|(mv, _)| mv),
+        }
+    }
+
+    /// Searches `depth` plies ahead and returns the best move with its evaluation, from the point
+    /// of view of the player on turn. A depth covering the remaining turns solves the position.
+    ///
+    /// Ties go to the first move in `Game::moves` order. Returns `None` if the search is aborted.
+    pub fn search(&self, game: &Game, depth: u8) -> Option<(Move, i32)> {
+        if self.is_aborted() {
+            return None;
+        }
+
+        let moves: Vec<Move> = game.moves().collect();
+        let values: Vec<AtomicI32> = moves.iter().map(|_| AtomicI32::new(-INF)).collect();
+        let alpha = AtomicI32::new(-INF);
+        let next = AtomicUsize::new(0);
+
+        let worker = || {
+            loop {
+                let j = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&mv) = moves.get(j) else {
+                    break;
+                };
+
+                let mut child = *game;
+                child.play(mv, self.rules, self.card_db);
+
+                let bound = alpha.load(Ordering::Relaxed);
+                let value = -self.negamax(&child, depth.saturating_sub(1), -INF, -bound);
+
+                // values at or below the bound are only upper bounds and can't be the best move
+                if value > bound {
+                    values[j].store(value, Ordering::Relaxed);
+                    alpha.fetch_max(value, Ordering::Relaxed);
+                }
+            }
+        };
+
+        if self.threads == 1 {
+            worker();
+        } else {
+            thread::scope(|scope| {
+                for _ in 0..self.threads.min(moves.len()) {
+                    scope.spawn(worker);
+                }
+            });
+        }
+
+        if self.is_aborted() {
+            return None;
+        }
+
+        let mut best: Option<(Move, i32)> = None;
+        for (&mv, value) in moves.iter().zip(&values) {
+            let value = value.load(Ordering::Relaxed);
+            if best.is_none_or(|(_, best_value)| value > best_value) {
+                best = Some((mv, value));
+            }
+        }
+
+        best
+    }
+
+    /// Returns the evaluation of `game` from the point of view of the player on turn.
+    pub fn evaluate(&self, game: &Game, depth: u8) -> i32 {
+        self.negamax(game, depth, -INF, INF)
+    }
+
+    fn negamax(&self, game: &Game, depth: u8, mut alpha: i32, mut beta: i32) -> i32 {
+        if game.is_over() {
+            return material(game, game.turn);
+        }
+        if depth == 0 {
+            return material(game, game.turn) + self.positional(game, game.turn);
+        }
+
+        // near the leaves hashing costs more than it saves
+        if depth < Self::TABLE_MIN_DEPTH {
+            return self.alphabeta(game, depth, alpha, beta);
+        }
+
+        // the result is discarded anyway, and must not reach the table
+        if self.is_aborted() {
+            return 0;
+        }
+
+        let hash = self.zobrist.hash(game);
+        if let Some(entry) = self.table.get(hash)
+            && entry.depth >= depth
+        {
+            match entry.bound {
+                Bound::Exact => return entry.value,
+                Bound::Lower => alpha = alpha.max(entry.value),
+                Bound::Upper => beta = beta.min(entry.value),
+            }
+            if alpha >= beta {
+                return entry.value;
+            }
+        }
+
+        let best = self.alphabeta(game, depth, alpha, beta);
+        if self.aborted.load(Ordering::Relaxed) {
+            return best;
+        }
+
+        let bound = if best <= alpha {
+            Bound::Upper
+        } else if best >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.table.insert(
+            hash,
+            Entry {
+                value: best,
+                depth,
+                bound,
+            },
+        );
+
+        best
+    }
+
+    fn is_aborted(&self) -> bool {
+        if self.aborted.load(Ordering::Relaxed) {
+            return true;
+        }
+
+        let cancelled = self
+            .cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed));
+        let late = self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline);
+
+        if cancelled || late {
+            self.aborted.store(true, Ordering::Relaxed);
+        }
+
+        cancelled || late
+    }
+
+    /// Searches the children of `game`, which must be neither over nor at depth 0.
+    fn alphabeta(&self, game: &Game, depth: u8, mut alpha: i32, beta: i32) -> i32 {
+        let mut best = -INF;
+        for mv in game.moves() {
+            let mut child = *game;
+            child.play(mv, self.rules, self.card_db);
+
+            let value = -self.negamax(&child, depth - 1, -beta, -alpha);
+            best = best.max(value);
+            alpha = alpha.max(value);
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        best
+    }
+
+    /// Weighted positional terms from `player`'s point of view.
+    fn positional(&self, game: &Game, player: Player) -> i32 {
+        let Weights {
+            corner,
+            exposed,
+            combo,
+        } = self.weights;
+
+        let combo_rules = self.rules.same || self.rules.plus;
+        let mut value = 0;
+
+        for (j, cell) in game.grid.iter().enumerate() {
+            let Some(coords) = BoardCoords::new(j % BOARD_SIZE, j / BOARD_SIZE) else {
+                continue;
+            };
+
+            match cell {
+                Some(cell) => {
+                    let sign = if cell.owner == player { 1 } else { -1 };
+                    let stats = &self.card_db.stats[cell.card.index()];
+
+                    if coords.is_corner() {
+                        value += sign * corner;
+                    }
+
+                    let weak = Direction::ALL
+                        .into_iter()
+                        .filter(|&dir| {
+                            coords
+                                .neighbor(dir)
+                                .is_some_and(|n| game.grid[n.index()].is_none())
+                        })
+                        .filter(|&dir| side(stats, dir) <= Weights::WEAK_SIDE)
+                        .count() as i32;
+                    value -= sign * weak * exposed;
+                }
+
+                None if combo_rules => {
+                    let opponents = Direction::ALL
+                        .into_iter()
+                        .filter_map(|dir| coords.neighbor(dir))
+                        .filter(|n| game.grid[n.index()].is_some_and(|c| c.owner != player))
+                        .count();
+                    if opponents >= 2 {
+                        value += combo;
+                    }
+                }
+
+                None => {}
+            }
+        }
+
+        value
+    }
+}
+
+// =========================================== Eval ================================================
+
+/// Returns the number of threads the machine can run in parallel.
+pub fn available_threads() -> usize {
+    thread::available_parallelism().map_or(1, NonZero::get)
+}
+
+const INF: i32 = i32::MAX;
+
+/// Card count difference from `player`'s point of view, in `Weights::CARD` units.
+fn material(game: &Game, player: Player) -> i32 {
+    (game.score(player) as i32 - game.score(!player) as i32) * Weights::CARD
+}
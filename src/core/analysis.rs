@@ -0,0 +1,170 @@
+use std::{
+    fmt,
+    sync::Arc,
+    thread::{self, JoinHandle},
+};
+
+use crate::{
+    core::{
+        ai::{SOLVE_DEPTH, Search, Weights, available_threads},
+        battle::Player,
+        game::{Game, Move},
+        record::{MatchRecord, RecordError},
+    },
+    data::CardDb,
+};
+
+/// Evaluation loss from which a move is reported as a blunder, i.e. at least one card given away.
+pub const BLUNDER_LOSS: i32 = 2;
+
+// ========================================= Analysis ==============================================
+
+/// Solver verdict on every move of a match.
+#[derive(Debug)]
+pub struct Analysis {
+    /// Position before each move, followed by the final position.
+    pub positions: Vec<Game>,
+    pub reports: Vec<MoveReport>,
+}
+
+impl Analysis {
+    pub fn new(record: &MatchRecord, card_db: &CardDb) -> Result<Self, RecordError> {
+        let positions = record.positions(card_db)?;
+        let search = Search::new(record.rules, card_db, Weights::default())
+            .with_threads(available_threads());
+
+        let reports = positions
+            .iter()
+            .zip(&record.moves)
+            .enumerate()
+            .filter_map(|(turn, (game, &chosen))| {
+                let (best, best_eval) = search.search(game, SOLVE_DEPTH)?;
+
+                let mut child = *game;
+                child.play(chosen, record.rules, card_db);
+                let chosen_eval = -search.evaluate(&child, SOLVE_DEPTH);
+
+                Some(MoveReport {
+                    turn,
+                    player: game.turn,
+                    chosen,
+                    chosen_eval: chosen_eval / Weights::CARD,
+                    best,
+                    best_eval: best_eval / Weights::CARD,
+                })
+            })
+            .collect();
+
+        Ok(Self { positions, reports })
+    }
+
+    /// Analyzes `record` on a background thread, as it takes a solve per move.
+    pub fn spawn(
+        record: MatchRecord,
+        card_db: Arc<CardDb>,
+    ) -> JoinHandle<Result<Self, RecordError>> {
+        thread::spawn(move || Self::new(&record, &card_db))
+    }
+
+    pub fn blunders(&self, player: Player) -> usize {
+        self.reports
+            .iter()
+            .filter(|report| report.player == player && report.is_blunder())
+            .count()
+    }
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "turn  player  chosen  eval  best    eval  loss")?;
+
+        for report in &self.reports {
+            writeln!(f, "{report}")?;
+        }
+
+        writeln!(f)?;
+        for player in [Player::P1, Player::P2] {
+            writeln!(f, "{player:?} blunders: {}", self.blunders(player))?;
+        }
+
+        Ok(())
+    }
+}
+
+// ======================================== MoveReport =============================================
+
+/// Chosen and best move of a turn, evaluated in cards from the mover's point of view.
+#[derive(Clone, Copy, Debug)]
+pub struct MoveReport {
+    pub turn: usize,
+    pub player: Player,
+    pub chosen: Move,
+    pub chosen_eval: i32,
+    pub best: Move,
+    pub best_eval: i32,
+}
+
+impl MoveReport {
+    pub fn loss(&self) -> i32 {
+        self.best_eval - self.chosen_eval
+    }
+
+    pub fn is_blunder(&self) -> bool {
+        self.loss() >= BLUNDER_LOSS
+    }
+}
+
+impl fmt::Display for MoveReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>4}  {:<6}  {:<6}  {:>+4}  {:<6}  {:>+4}  {:>4}",
+            self.turn + 1,
+            format!("{:?}", self.player),
+            self.chosen.to_string(),
+            self.chosen_eval,
+            self.best.to_string(),
+            self.best_eval,
+            self.loss(),
+        )?;
+
+        if self.is_blunder() {
+            write!(f, "  blunder")?;
+        }
+
+        Ok(())
+    }
+}
+
+// ========================================== Review ===============================================
+
+/// Turn by turn walk through an `Analysis`.
+#[derive(Debug)]
+pub struct Review {
+    pub analysis: Analysis,
+    pub turn: usize,
+}
+
+impl Review {
+    pub fn new(analysis: Analysis) -> Self {
+        Self { analysis, turn: 0 }
+    }
+
+    pub fn next(&mut self) {
+        self.turn = (self.turn + 1).min(self.analysis.positions.len().saturating_sub(1));
+    }
+
+    pub fn prev(&mut self) {
+        self.turn = self.turn.saturating_sub(1);
+    }
+
+    /// Position before the move of the current turn.
+    pub fn position(&self) -> Option<&Game> {
+        self.analysis.positions.get(self.turn)
+    }
+
+    /// Report of the current turn, `None` on the final position.
+    pub fn report(&self) -> Option<&MoveReport> {
+        self.analysis.reports.get(self.turn)
+    }
+}
@@ -55,12 +55,32 @@ impl From<BattleSetup> for Battle {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct BattleSetup {
     pub p1_hand: [CardId; HAND_SIZE],
     pub p2_hand: [CardId; HAND_SIZE],
 }
 
+impl BattleSetup {
+    /// Deals each player the cards they own at the end of a match, as Sudden Death does.
+    ///
+    /// Returns `None` unless both players own exactly `HAND_SIZE` cards.
+    pub fn rematch(components: &Components) -> Option<Self> {
+        let hand = |player: Player| -> Option<[CardId; HAND_SIZE]> {
+            let cards: Vec<CardId> = Entity::iter()
+                .filter(|&e| components.owner[e] == Some(player))
+                .filter_map(|e| components.card[e])
+                .collect();
+            cards.try_into().ok()
+        };
+
+        Some(Self {
+            p1_hand: hand(Player::P1)?,
+            p2_hand: hand(Player::P2)?,
+        })
+    }
+}
+
 // ========================================= Components ============================================
 
 #[derive(Debug, Default)]
@@ -231,6 +251,10 @@ impl BoardCoords {
         self.1
     }
 
+    pub fn is_corner(&self) -> bool {
+        (self.0 == 0 || self.0 == BOARD_SIZE - 1) && (self.1 == 0 || self.1 == BOARD_SIZE - 1)
+    }
+
     pub fn moved_down(&self) -> Self {
         Self(self.0, (self.1 + 1) % BOARD_SIZE)
     }
@@ -263,7 +287,7 @@ impl BoardCoords {
 }
 
 /// Cardinal direction on the board.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Direction {
     Down,
     Left,
@@ -271,6 +295,24 @@ pub enum Direction {
     Up,
 }
 
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+        Direction::Up,
+    ];
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            Direction::Up => Direction::Down,
+        }
+    }
+}
+
 //============================================ State ===============================================
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -0,0 +1,284 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use crate::{
+    core::{
+        ai::Search,
+        battle::{BOARD_SIZE, BoardCoords},
+        data::CardId,
+        game::{Game, Move},
+        rules::RuleSet,
+        table::Zobrist,
+    },
+    data::CardDb,
+};
+
+// ======================================= OpeningBook =============================================
+
+/// Precomputed moves for the first placements of a match, generated offline by deep search.
+///
+/// Positions are keyed by `Zobrist::hash_mover`, the board and the hand of the player on turn,
+/// mixed with the capture rules. The opponent's hand is left out so that a book move found for one
+/// deal is still played when the opponent holds other cards, as long as it's legal. A move is
+/// stored as the card to play rather than its hand index, as the hash ignores hand order. Open and
+/// Sudden Death don't change the best move of a search that sees both hands.
+///
+/// The file starts with the `MAGIC` bytes, the format version, the search depth, the fingerprints
+/// of the card database and of the `Zobrist` keys, and the entry count, followed by the entries
+/// sorted by key: the key, the card index and the cell index, little endian. A book is only loaded
+/// along with the cards and keys it was generated with.
+pub struct OpeningBook {
+    /// Depth of the search that generated the book.
+    pub depth: u8,
+    entries: Vec<BookEntry>,
+    zobrist: Zobrist,
+    /// `CardDb::fingerprint` of the cards the book was generated with.
+    db_fingerprint: u32,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+struct BookEntry {
+    key: u64,
+    card: u8,
+    cell: u8,
+}
+
+impl OpeningBook {
+    pub const MAGIC: [u8; 4] = *b"TTOB";
+    pub const VERSION: u8 = 4;
+
+    /// Positions with fewer cards placed are covered by the book.
+    pub const PLIES: usize = 2;
+
+    pub fn new(depth: u8, card_db: &CardDb) -> Self {
+        Self {
+            depth,
+            entries: Vec::new(),
+            zobrist: Zobrist::new(),
+            db_fingerprint: card_db.fingerprint(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Adds the book moves of a deal: the opening of `game`, and the reply to each of its first
+    /// placements so that it's in the book whatever the opening.
+    pub fn add_deal(&mut self, game: &Game, rules: RuleSet, search: &Search) {
+        if let Some((mv, _)) = search.search(game, self.depth) {
+            self.insert(game, rules, mv);
+        }
+
+        for first in game.moves() {
+            let mut child = *game;
+            child.play(first, rules, search.card_db);
+
+            if let Some((mv, _)) = search.search(&child, self.depth) {
+                self.insert(&child, rules, mv);
+            }
+        }
+    }
+
+    /// Returns the book move for `game`, if any and legal.
+    pub fn get(&self, game: &Game, rules: RuleSet) -> Option<Move> {
+        if game.placed_count() >= Self::PLIES {
+            return None;
+        }
+
+        let key = self.key(game, rules);
+        let j = self
+            .entries
+            .binary_search_by_key(&key, |entry| entry.key)
+            .ok()?;
+        let entry = self.entries[j];
+
+        let hand = game
+            .hand(game.turn)
+            .position(|card| card.index() == entry.card as usize)?;
+        let cell = entry.cell as usize;
+        let cell = BoardCoords::new(cell % BOARD_SIZE, cell / BOARD_SIZE)?;
+
+        (game.grid[cell.index()].is_none()).then_some(Move { hand, cell })
+    }
+
+    /// Adds or replaces the book move for `game`.
+    pub fn insert(&mut self, game: &Game, rules: RuleSet, mv: Move) {
+        let Some(card) = game.hand(game.turn).nth(mv.hand) else {
+            return;
+        };
+
+        let entry = BookEntry {
+            key: self.key(game, rules),
+            card: card.index() as u8,
+            cell: mv.cell.index() as u8,
+        };
+
+        match self
+            .entries
+            .binary_search_by_key(&entry.key, |entry| entry.key)
+        {
+            Ok(j) => self.entries[j] = entry,
+            Err(j) => self.entries.insert(j, entry),
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>, card_db: &CardDb) -> io::Result<Self> {
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+
+        let mut reader = BufReader::new(File::open(path)?);
+
+        let mut header = [0u8; 6];
+        reader.read_exact(&mut header)?;
+        let [m0, m1, m2, m3, version, depth] = header;
+
+        if [m0, m1, m2, m3] != Self::MAGIC {
+            return Err(invalid("not an opening book"));
+        }
+        if version != Self::VERSION {
+            return Err(invalid("unsupported opening book version"));
+        }
+
+        let mut fingerprints = [0u8; 12];
+        reader.read_exact(&mut fingerprints)?;
+        let [d0, d1, d2, d3, z0, z1, z2, z3, z4, z5, z6, z7] = fingerprints;
+
+        let zobrist = Zobrist::new();
+        if u32::from_le_bytes([d0, d1, d2, d3]) != card_db.fingerprint() {
+            return Err(invalid("opening book was generated for other cards"));
+        }
+        if u64::from_le_bytes([z0, z1, z2, z3, z4, z5, z6, z7]) != zobrist.fingerprint() {
+            return Err(invalid("opening book was generated with other hash keys"));
+        }
+
+        let mut count = [0u8; 4];
+        reader.read_exact(&mut count)?;
+        let count = u32::from_le_bytes(count);
+
+        // the count isn't trusted with an allocation, a short file fails as it's read
+        let mut entries = Vec::new();
+        let mut buf = [0u8; 10];
+
+        for _ in 0..count {
+            reader.read_exact(&mut buf)?;
+            let [k0, k1, k2, k3, k4, k5, k6, k7, card, cell] = buf;
+
+            if CardId::new(card).is_none_or(|card| card.index() >= card_db.stats.len())
+                || cell as usize >= BOARD_SIZE * BOARD_SIZE
+            {
+                return Err(invalid("opening book entry out of range"));
+            }
+
+            entries.push(BookEntry {
+                key: u64::from_le_bytes([k0, k1, k2, k3, k4, k5, k6, k7]),
+                card,
+                cell,
+            });
+        }
+
+        if !entries.is_sorted() {
+            return Err(invalid("opening book entries are not sorted"));
+        }
+
+        Ok(Self {
+            depth,
+            entries,
+            zobrist,
+            db_fingerprint: card_db.fingerprint(),
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        writer.write_all(&Self::MAGIC)?;
+        writer.write_all(&[Self::VERSION, self.depth])?;
+        writer.write_all(&self.db_fingerprint.to_le_bytes())?;
+        writer.write_all(&self.zobrist.fingerprint().to_le_bytes())?;
+        writer.write_all(&(self.entries.len() as u32).to_le_bytes())?;
+
+        for entry in &self.entries {
+            writer.write_all(&entry.key.to_le_bytes())?;
+            writer.write_all(&[entry.card, entry.cell])?;
+        }
+
+        writer.flush()
+    }
+
+    fn key(&self, game: &Game, rules: RuleSet) -> u64 {
+        const SAME: u64 = 0x9e37_79b9_7f4a_7c15;
+        const PLUS: u64 = 0xbf58_476d_1ce4_e5b9;
+
+        let mut key = self.zobrist.hash_mover(game);
+        if rules.same {
+            key ^= SAME;
+        }
+        if rules.plus {
+            key ^= PLUS;
+        }
+
+        key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::{
+            ai::Weights,
+            battle::{BattleSetup, Player, Pool},
+            card_pools::CardPool,
+            npc::Npc,
+        },
+        profile::Profile,
+        sys::rand::Rng,
+    };
+
+    #[test]
+    fn book_hits_the_reply_to_the_player_opening() {
+        let card_db = CardDb::embedded().unwrap();
+        let npc = Npc::find("grandmaster").unwrap();
+        let pools = CardPool::load_all("config/pools").unwrap();
+        let opponent_pool = CardPool::resolve_named(&pools, npc.pool, &card_db).unwrap();
+        let player_pool: Pool = Profile::new(&card_db).inventory.iter_distinct().collect();
+        let mut rng = Rng::from_seed(7);
+
+        // built as `opening-book` does
+        let search = Search::new(npc.rules, &card_db, Weights::default());
+        let mut book = OpeningBook::new(2, &card_db);
+        let dealt = BattleSetup {
+            p1_hand: player_pool.draw_hand(&mut rng),
+            p2_hand: opponent_pool.draw_hand(&mut rng),
+        };
+        book.add_deal(&Game::new(&dealt), npc.rules, &search);
+
+        let path = std::env::temp_dir().join("triple-triad-test.book");
+        book.save(&path).unwrap();
+        let book = OpeningBook::load(&path, &card_db).unwrap();
+
+        // set up as `main` does, the player holding other cards than the ones the book was built
+        // with but for the opening card
+        let mut setup = BattleSetup {
+            p1_hand: player_pool.draw_hand(&mut rng),
+            p2_hand: dealt.p2_hand,
+        };
+        setup.p1_hand[0] = dealt.p1_hand[0];
+        assert_ne!(setup.p1_hand, dealt.p1_hand);
+
+        let mut game = Game::new(&setup);
+        let opening = game.moves().next().unwrap();
+        game.play(opening, npc.rules, &card_db);
+        assert_eq!(game.turn, Player::P2);
+
+        let mv = book.get(&game, npc.rules).unwrap();
+        assert!(game.moves().any(|legal| legal == mv));
+    }
+}
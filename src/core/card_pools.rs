@@ -1,152 +1,251 @@
-use crate::core::{battle::Pool, data::CardId};
-use std::borrow::Cow;
-
-pub const POOL_BEGINNER: Pool = Pool(Cow::Borrowed(&[
-    CardId::new_const(0),
-    CardId::new_const(1),
-    CardId::new_const(2),
-    CardId::new_const(3),
-    CardId::new_const(4),
-    CardId::new_const(5),
-    CardId::new_const(6),
-    CardId::new_const(7),
-    CardId::new_const(8),
-    CardId::new_const(9),
-    CardId::new_const(10),
-    CardId::new_const(11),
-    CardId::new_const(12),
-    CardId::new_const(13),
-    CardId::new_const(14),
-    CardId::new_const(15),
-    CardId::new_const(16),
-    CardId::new_const(17),
-    CardId::new_const(18),
-    CardId::new_const(19),
-    CardId::new_const(20),
-    CardId::new_const(21),
-]));
-
-pub const POOL_NOVICE: Pool = Pool(Cow::Borrowed(&[
-    CardId::new_const(11),
-    CardId::new_const(12),
-    CardId::new_const(13),
-    CardId::new_const(14),
-    CardId::new_const(15),
-    CardId::new_const(16),
-    CardId::new_const(17),
-    CardId::new_const(18),
-    CardId::new_const(19),
-    CardId::new_const(20),
-    CardId::new_const(21),
-    CardId::new_const(22),
-    CardId::new_const(23),
-    CardId::new_const(24),
-    CardId::new_const(25),
-    CardId::new_const(26),
-    CardId::new_const(27),
-    CardId::new_const(28),
-    CardId::new_const(29),
-    CardId::new_const(30),
-    CardId::new_const(31),
-    CardId::new_const(32),
-]));
-
-pub const POOL_APPRENTICE: Pool = Pool(Cow::Borrowed(&[
-    CardId::new_const(22),
-    CardId::new_const(23),
-    CardId::new_const(24),
-    CardId::new_const(25),
-    CardId::new_const(26),
-    CardId::new_const(27),
-    CardId::new_const(28),
-    CardId::new_const(29),
-    CardId::new_const(30),
-    CardId::new_const(31),
-    CardId::new_const(32),
-    CardId::new_const(33),
-    CardId::new_const(34),
-    CardId::new_const(35),
-    CardId::new_const(36),
-    CardId::new_const(37),
-    CardId::new_const(38),
-    CardId::new_const(39),
-    CardId::new_const(40),
-    CardId::new_const(41),
-    CardId::new_const(42),
-    CardId::new_const(43),
-]));
-
-pub const POOL_JOURNEYMAN: Pool = Pool(Cow::Borrowed(&[
-    CardId::new_const(33),
-    CardId::new_const(34),
-    CardId::new_const(35),
-    CardId::new_const(36),
-    CardId::new_const(37),
-    CardId::new_const(38),
-    CardId::new_const(39),
-    CardId::new_const(40),
-    CardId::new_const(41),
-    CardId::new_const(42),
-    CardId::new_const(43),
-    CardId::new_const(44),
-    CardId::new_const(45),
-    CardId::new_const(46),
-    CardId::new_const(47),
-    CardId::new_const(48),
-    CardId::new_const(49),
-    CardId::new_const(50),
-    CardId::new_const(51),
-    CardId::new_const(52),
-    CardId::new_const(53),
-    CardId::new_const(54),
-]));
-
-pub const POOL_MASTER: Pool = Pool(Cow::Borrowed(&[
-    CardId::new_const(44),
-    CardId::new_const(45),
-    CardId::new_const(46),
-    CardId::new_const(47),
-    CardId::new_const(48),
-    CardId::new_const(49),
-    CardId::new_const(50),
-    CardId::new_const(51),
-    CardId::new_const(52),
-    CardId::new_const(53),
-    CardId::new_const(54),
-    CardId::new_const(55),
-    CardId::new_const(56),
-    CardId::new_const(57),
-    CardId::new_const(58),
-    CardId::new_const(59),
-    CardId::new_const(60),
-    CardId::new_const(61),
-    CardId::new_const(62),
-    CardId::new_const(63),
-    CardId::new_const(64),
-    CardId::new_const(65),
-]));
-
-pub const POOL_GRANDMASTER: Pool = Pool(Cow::Borrowed(&[
-    CardId::new_const(55),
-    CardId::new_const(56),
-    CardId::new_const(57),
-    CardId::new_const(58),
-    CardId::new_const(59),
-    CardId::new_const(60),
-    CardId::new_const(61),
-    CardId::new_const(62),
-    CardId::new_const(63),
-    CardId::new_const(64),
-    CardId::new_const(65),
-    CardId::new_const(66),
-    CardId::new_const(67),
-    CardId::new_const(68),
-    CardId::new_const(69),
-    CardId::new_const(70),
-    CardId::new_const(71),
-    CardId::new_const(72),
-    CardId::new_const(73),
-    CardId::new_const(74),
-    CardId::new_const(75),
-    CardId::new_const(76),
-]));
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    ops::RangeInclusive,
+    path::Path,
+    str::FromStr,
+};
+
+use crate::{
+    core::battle::{HAND_SIZE, Pool},
+    data::{CardDb, CardQuery},
+};
+
+// ========================================= CardPool ==============================================
+
+/// Named pool of cards an NPC draws its hands from.
+///
+/// Pools are stored one per line as `name,entry,...`, where an entry is a card id, a card name or a
+/// level range such as `level=3` or `level=1..2`. Ids and names may be namespaced, as resolved by
+/// `CardDb::resolve`. A name holding commas is put between double quotes, as a CSV field, and a
+/// quoted entry is always a card.
+#[derive(Clone, Debug)]
+pub struct CardPool {
+    pub name: String,
+    pub entries: Vec<PoolEntry>,
+}
+
+#[derive(Clone, Debug)]
+pub enum PoolEntry {
+    /// A card id or name.
+    Card(String),
+    Levels(RangeInclusive<u8>),
+}
+
+impl CardPool {
+    const FIELD_SEPARATOR: char = ',';
+
+    pub fn load_all(path: impl AsRef<Path>) -> io::Result<Vec<Self>> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut pools = Vec::new();
+
+        for (j, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let pool = line.parse().map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("pool at line {}: {e}", j + 1),
+                )
+            })?;
+            pools.push(pool);
+        }
+
+        Ok(pools)
+    }
+
+    /// Looks up a pool by case insensitive name.
+    pub fn find<'a>(pools: &'a [Self], name: &str) -> Option<&'a Self> {
+        pools
+            .iter()
+            .find(|pool| pool.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Resolves the pool named `name`, see `resolve`.
+    pub fn resolve_named(
+        pools: &[Self],
+        name: &str,
+        card_db: &CardDb,
+    ) -> Result<Pool<'static>, PoolError> {
+        Self::find(pools, name)
+            .ok_or(PoolError::UnknownPool)?
+            .resolve(card_db)
+    }
+
+    /// Returns the cards of the pool, each once, failing on cards unknown to `card_db` or when
+    /// there are too few cards to draw a hand from.
+    pub fn resolve(&self, card_db: &CardDb) -> Result<Pool<'static>, PoolError> {
+        let mut cards = Vec::new();
+
+        for entry in &self.entries {
+            match entry {
+                PoolEntry::Card(id) => {
+                    let card = card_db
+                        .resolve(id)
+                        .ok_or_else(|| PoolError::UnknownCard(id.clone()))?;
+                    cards.push(card);
+                }
+                PoolEntry::Levels(levels) => {
+                    let query = CardQuery::new().with_levels(levels.clone());
+                    cards.extend(card_db.query(&query));
+                }
+            }
+        }
+
+        let mut pool: Vec<_> = Vec::with_capacity(cards.len());
+        for card in cards {
+            if !pool.contains(&card) {
+                pool.push(card);
+            }
+        }
+
+        if pool.len() <= HAND_SIZE {
+            return Err(PoolError::TooFewCards(pool.len()));
+        }
+
+        Ok(pool.into_iter().collect())
+    }
+}
+
+impl FromStr for CardPool {
+    type Err = PoolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = split_fields(s)?.into_iter();
+
+        let name = parts
+            .next()
+            .map(|(name, _)| name)
+            .filter(|name| !name.is_empty())
+            .ok_or(PoolError::InvalidName)?;
+
+        let entries = parts
+            .map(|(entry, quoted)| {
+                let Some(levels) = entry.strip_prefix("level=").filter(|_| !quoted) else {
+                    return match entry.as_str() {
+                        "" => Err(PoolError::InvalidEntry(entry)),
+                        _ => Ok(PoolEntry::Card(entry)),
+                    };
+                };
+
+                let err = || PoolError::InvalidEntry(entry.clone());
+                let (min, max) = levels.split_once("..").unwrap_or((levels, levels));
+                Ok(PoolEntry::Levels(
+                    min.parse().map_err(|_| err())?..=max.parse().map_err(|_| err())?,
+                ))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(CardPool { name, entries })
+    }
+}
+
+/// Splits `s` on the field separator into trimmed fields, telling whether each was quoted.
+///
+/// A quoted field keeps separators and spaces as is, its quotes being doubled.
+fn split_fields(s: &str) -> Result<Vec<(String, bool)>, PoolError> {
+    let mut fields = Vec::new();
+    let mut rest = s;
+
+    loop {
+        let trimmed = rest.trim_start();
+        let (field, quoted, after) = match trimmed.strip_prefix('"') {
+            Some(inner) => {
+                let mut field = String::new();
+                let mut chars = inner.char_indices();
+                let end = loop {
+                    match chars.next() {
+                        Some((j, '"')) if inner[j + 1..].starts_with('"') => {
+                            field.push('"');
+                            chars.next();
+                        }
+                        Some((j, '"')) => break j + 1,
+                        Some((_, c)) => field.push(c),
+                        None => return Err(PoolError::InvalidEntry(trimmed.to_string())),
+                    }
+                };
+
+                let after = inner[end..].trim_start();
+                if !after.is_empty() && !after.starts_with(CardPool::FIELD_SEPARATOR) {
+                    return Err(PoolError::InvalidEntry(trimmed.to_string()));
+                }
+                (field, true, after)
+            }
+            None => {
+                let end = trimmed
+                    .find(CardPool::FIELD_SEPARATOR)
+                    .unwrap_or(trimmed.len());
+                (trimmed[..end].trim().to_string(), false, &trimmed[end..])
+            }
+        };
+        fields.push((field, quoted));
+
+        match after.strip_prefix(CardPool::FIELD_SEPARATOR) {
+            Some(after) => rest = after,
+            None => return Ok(fields),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum PoolError {
+    InvalidEntry(String),
+    InvalidName,
+    TooFewCards(usize),
+    UnknownCard(String),
+    UnknownPool,
+}
+
+impl fmt::Display for PoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoolError::InvalidEntry(entry) => write!(f, "invalid pool entry '{entry}'"),
+            PoolError::InvalidName => write!(f, "invalid pool name"),
+            PoolError::TooFewCards(count) => write!(
+                f,
+                "{count} cards, a pool needs more than {HAND_SIZE} to draw hands from"
+            ),
+            PoolError::UnknownCard(id) => write!(f, "unknown card '{id}'"),
+            PoolError::UnknownPool => write!(f, "unknown pool"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_names_hold_commas_and_quotes() {
+        let pool: CardPool = r#"dlc, "Chocobo, Fat", level=2, "Say ""Cheese""",ns:3"#
+            .parse()
+            .unwrap();
+
+        assert_eq!(pool.name, "dlc");
+        let entries: Vec<_> = pool
+            .entries
+            .iter()
+            .map(|entry| match entry {
+                PoolEntry::Card(id) => id.clone(),
+                PoolEntry::Levels(levels) => format!("{levels:?}"),
+            })
+            .collect();
+        assert_eq!(
+            entries,
+            ["Chocobo, Fat", "2..=2", r#"Say "Cheese""#, "ns:3"]
+        );
+
+        for line in [
+            r#"dlc,"Chocobo"#,
+            r#"dlc,"Chocobo" Fat"#,
+            r#"dlc,"""#,
+            "dlc,,Geezard",
+        ] {
+            assert!(line.parse::<CardPool>().is_err(), "{line}");
+        }
+    }
+}
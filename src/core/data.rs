@@ -1,10 +1,11 @@
 // =========================================== CardId ==============================================
 
+/// Index of a card in the `CardDb`, shared by the base set and the expansions.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct CardId(u8);
 
 impl CardId {
-    pub const MAX: u8 = 110;
+    pub const MAX: u8 = u8::MAX;
 
     pub fn new(index: u8) -> Option<Self> {
         if index < Self::MAX {
@@ -0,0 +1,167 @@
+use std::fmt;
+
+use crate::{
+    core::{
+        battle::{
+            BOARD_SIZE, BattleResult, BattleSetup, BoardCoords, Components, Entity, HAND_SIZE,
+            Player, Position,
+        },
+        data::CardId,
+        rules::{Cell, Flips, Grid, RuleSet, captures},
+    },
+    data::CardDb,
+};
+
+// =========================================== Game ================================================
+
+/// Compact, self contained snapshot of a match, suitable for search and headless play.
+///
+/// Hands are kept compact the same way `placement_system` shifts `Position::Hand`, so a
+/// `Move::hand` is also the hand cursor of the ECS match.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Game {
+    pub grid: Grid,
+    pub hands: [[Option<CardId>; HAND_SIZE]; 2],
+    pub turn: Player,
+}
+
+impl Game {
+    pub fn new(setup: &BattleSetup) -> Self {
+        Self {
+            grid: [None; BOARD_SIZE * BOARD_SIZE],
+            hands: [setup.p1_hand.map(Some), setup.p2_hand.map(Some)],
+            turn: Player::P1,
+        }
+    }
+
+    pub fn hand(&self, player: Player) -> impl Iterator<Item = CardId> + '_ {
+        self.hands[player as usize].iter().map_while(|&card| card)
+    }
+
+    pub fn hand_size(&self, player: Player) -> usize {
+        self.hand(player).count()
+    }
+
+    /// Returns every legal move for the player on turn.
+    pub fn moves(&self) -> impl Iterator<Item = Move> + '_ {
+        let hand_size = self.hand_size(self.turn);
+
+        (0..hand_size).flat_map(move |hand| {
+            (0..BOARD_SIZE * BOARD_SIZE)
+                .filter(|&j| self.grid[j].is_none())
+                .filter_map(move |j| {
+                    let cell = BoardCoords::new(j % BOARD_SIZE, j / BOARD_SIZE)?;
+                    Some(Move { hand, cell })
+                })
+        })
+    }
+
+    /// Plays `mv` for the player on turn, applies captures and passes the turn.
+    ///
+    /// Returns the captured cells. Illegal moves are ignored and return no flips.
+    pub fn play(&mut self, mv: Move, rules: RuleSet, card_db: &CardDb) -> Flips {
+        let hand = &mut self.hands[self.turn as usize];
+
+        let Some(card) = hand.get(mv.hand).copied().flatten() else {
+            return Flips::default();
+        };
+        if self.grid[mv.cell.index()].is_some() {
+            return Flips::default();
+        }
+
+        hand.copy_within(mv.hand + 1.., mv.hand);
+        hand[HAND_SIZE - 1] = None;
+
+        self.grid[mv.cell.index()] = Some(Cell {
+            card,
+            owner: self.turn,
+        });
+
+        let flips = captures(&self.grid, mv.cell, rules, card_db);
+        for coords in flips.iter() {
+            if let Some(cell) = self.grid[coords.index()].as_mut() {
+                cell.owner = self.turn;
+            }
+        }
+
+        self.turn = !self.turn;
+
+        flips
+    }
+
+    /// Returns the number of cards owned by `player`, both placed and in hand.
+    pub fn score(&self, player: Player) -> usize {
+        let placed = self
+            .grid
+            .iter()
+            .flatten()
+            .filter(|cell| cell.owner == player)
+            .count();
+
+        placed + self.hand_size(player)
+    }
+
+    /// Rebuilds the ECS components of this position, e.g. to render it.
+    pub fn components(&self) -> Components {
+        let mut components = Components::default();
+
+        let placed = self.grid.iter().enumerate().filter_map(|(j, cell)| {
+            let cell = (*cell)?;
+            let coords = BoardCoords::new(j % BOARD_SIZE, j / BOARD_SIZE)?;
+            Some((cell.card, cell.owner, Position::Board(coords)))
+        });
+        let held = [Player::P1, Player::P2].into_iter().flat_map(|player| {
+            self.hand(player)
+                .enumerate()
+                .map(move |(j, card)| (card, player, Position::Hand(j)))
+        });
+
+        for (entity, (card, owner, position)) in Entity::iter().zip(placed.chain(held)) {
+            components.card.insert(entity, card);
+            components.owner.insert(entity, owner);
+            components.position.insert(entity, position);
+        }
+
+        components
+    }
+
+    pub fn placed_count(&self) -> usize {
+        self.grid.iter().flatten().count()
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.placed_count() == BOARD_SIZE * BOARD_SIZE
+    }
+
+    pub fn result(&self) -> Option<BattleResult> {
+        if !self.is_over() {
+            return None;
+        }
+
+        let p1_score = self.score(Player::P1);
+        let p2_score = self.score(Player::P2);
+
+        Some(if p1_score == p2_score {
+            BattleResult::Draw
+        } else if p1_score > p2_score {
+            BattleResult::Win(Player::P1)
+        } else {
+            BattleResult::Win(Player::P2)
+        })
+    }
+}
+
+// =========================================== Move ================================================
+
+/// Places the card at `hand` of the player on turn into `cell`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Move {
+    pub hand: usize,
+    pub cell: BoardCoords,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{},{}", self.hand, self.cell.x(), self.cell.y())
+    }
+}
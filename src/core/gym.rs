@@ -0,0 +1,288 @@
+use std::{io, path::Path};
+
+use crate::{
+    core::{
+        ai::{Search, Strength, Weights},
+        battle::{BOARD_SIZE, BattleResult, BattleSetup, BoardCoords, HAND_SIZE, Player, Pool},
+        data::CardId,
+        game::{Game, Move},
+        record::MatchRecord,
+        rules::RuleSet,
+    },
+    data::CardDb,
+    sys::rand::Rng,
+};
+
+/// Number of actions: a hand index times a board cell, `hand * 9 + cell`.
+pub const ACTIONS: usize = HAND_SIZE * CELLS;
+
+/// Length of an `Observation`.
+pub const OBSERVATION_SIZE: usize = CELLS * CELL_FEATURES + 2 * HAND_SIZE * CARD_FEATURES + 7;
+
+/// Fixed-size encoding of a position from the point of view of the observing player.
+///
+/// In order, each value being within `0.0..=1.0`:
+/// - per board cell: occupied, owned by the player, owned by the opponent, then the card sides
+///   top, right, bottom and left;
+/// - per card of the player's hand, then of the opponent's: present, then the sides, which are
+///   zero for an opponent's card if the hand is closed;
+/// - the rules open, same, plus and sudden death;
+/// - both scores and the number of cards placed.
+pub type Observation = [f32; OBSERVATION_SIZE];
+
+/// Legal actions of the player on turn, indexed like actions.
+pub type ActionMask = [bool; ACTIONS];
+
+const CELLS: usize = BOARD_SIZE * BOARD_SIZE;
+const CELL_FEATURES: usize = 7;
+const CARD_FEATURES: usize = 5;
+
+// ============================================ Env ================================================
+
+/// Gym-style environment over the headless engine, for training agents.
+///
+/// With an opponent, the agent plays `learner` and the opponent's moves are made within `step`.
+/// Without, the agent plays both seats and observes the position of whoever is on turn.
+///
+/// Rewards are sparse: 1 for a win, -1 for a loss and 0 for a draw, given on the step ending the
+/// match to the player acting in it. An illegal action is rewarded -1 and leaves the position
+/// unchanged, for the agent to act again. Once the match is over, every step is done and rewarded
+/// 0 until `reset`.
+pub struct Env<'a> {
+    pub rules: RuleSet,
+    pub card_db: &'a CardDb,
+    pub pool: &'a Pool<'a>,
+    pub opponent: Option<(Strength, Weights)>,
+    pub learner: Player,
+    search: Search<'a>,
+    game: Game,
+    record: MatchRecord,
+    rng: Rng,
+}
+
+impl<'a> Env<'a> {
+    /// Creates an environment dealt as by `reset(0)`, without opponent.
+    pub fn new(rules: RuleSet, card_db: &'a CardDb, pool: &'a Pool<'a>) -> Self {
+        let mut rng = Rng::from_seed(0);
+        let setup = BattleSetup {
+            p1_hand: pool.draw_hand(&mut rng),
+            p2_hand: pool.draw_hand(&mut rng),
+        };
+
+        Self {
+            rules,
+            card_db,
+            pool,
+            opponent: None,
+            learner: Player::P1,
+            search: Search::new(rules, card_db, Weights::default()),
+            game: Game::new(&setup),
+            record: MatchRecord::new(rules, setup),
+            rng,
+        }
+    }
+
+    /// Has the seat other than `learner` played by the AI.
+    pub fn with_opponent(mut self, strength: Strength, weights: Weights) -> Self {
+        self.opponent = Some((strength, weights));
+        self.search = Search::new(self.rules, self.card_db, weights);
+        self
+    }
+
+    pub fn with_learner(mut self, learner: Player) -> Self {
+        self.learner = learner;
+        self
+    }
+
+    /// Deals new hands from `seed` and returns the first observation of the agent.
+    pub fn reset(&mut self, seed: u64) -> Observation {
+        self.rng = Rng::from_seed(seed);
+
+        let setup = BattleSetup {
+            p1_hand: self.pool.draw_hand(&mut self.rng),
+            p2_hand: self.pool.draw_hand(&mut self.rng),
+        };
+        self.game = Game::new(&setup);
+        self.record = MatchRecord::new(self.rules, setup);
+
+        self.play_opponent();
+        self.observe()
+    }
+
+    /// Plays `action` for the agent, then the opponent's reply if any.
+    pub fn step(&mut self, action: usize) -> (Observation, f32, bool) {
+        if self.game.is_over() {
+            return (self.observe(), 0.0, true);
+        }
+
+        let mover = self.game.turn;
+
+        let Some(mv) = Self::decode(action).filter(|_| self.action_mask()[action]) else {
+            return (self.observe(), -1.0, false);
+        };
+        self.play(mv);
+        self.play_opponent();
+
+        let reward = match self.game.result() {
+            Some(BattleResult::Win(winner)) if winner == mover => 1.0,
+            Some(BattleResult::Win(_)) => -1.0,
+            _ => 0.0,
+        };
+
+        (self.observe(), reward, self.game.is_over())
+    }
+
+    pub fn action_mask(&self) -> ActionMask {
+        let mut mask = [false; ACTIONS];
+        for mv in self.game.moves() {
+            mask[Self::encode(mv)] = true;
+        }
+        mask
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Returns the current episode as a match record.
+    pub fn episode(&self) -> &MatchRecord {
+        &self.record
+    }
+
+    /// Saves the current episode, which `analyze` reads like any match record.
+    pub fn save_episode(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.record.save(path)
+    }
+
+    pub fn encode(mv: Move) -> usize {
+        mv.hand * CELLS + mv.cell.index()
+    }
+
+    pub fn decode(action: usize) -> Option<Move> {
+        let (hand, cell) = (action / CELLS, action % CELLS);
+        if hand >= HAND_SIZE {
+            return None;
+        }
+
+        Some(Move {
+            hand,
+            cell: BoardCoords::new(cell % BOARD_SIZE, cell / BOARD_SIZE)?,
+        })
+    }
+
+    /// Returns the observation of the agent: of `learner` with an opponent, including once the
+    /// match is over, of the player on turn otherwise.
+    pub fn observe(&self) -> Observation {
+        let mut obs = [0.0; OBSERVATION_SIZE];
+        let player = match self.opponent {
+            Some(_) => self.learner,
+            None => self.game.turn,
+        };
+        let mut j = 0;
+
+        let mut push = |value: f32| {
+            obs[j] = value;
+            j += 1;
+        };
+
+        for cell in &self.game.grid {
+            match cell {
+                Some(cell) => {
+                    push(1.0);
+                    push(flag(cell.owner == player));
+                    push(flag(cell.owner != player));
+                    for side in self.sides(cell.card) {
+                        push(side);
+                    }
+                }
+                None => (0..CELL_FEATURES).for_each(|_| push(0.0)),
+            }
+        }
+
+        for (hand_player, visible) in [(player, true), (!player, self.rules.open)] {
+            for card in self.game.hands[hand_player as usize] {
+                match card {
+                    Some(card) => {
+                        push(1.0);
+                        for side in self.sides(card) {
+                            push(if visible { side } else { 0.0 });
+                        }
+                    }
+                    None => (0..CARD_FEATURES).for_each(|_| push(0.0)),
+                }
+            }
+        }
+
+        let RuleSet {
+            open,
+            same,
+            plus,
+            sudden_death,
+        } = self.rules;
+        for rule in [open, same, plus, sudden_death] {
+            push(flag(rule));
+        }
+
+        let cards = (2 * HAND_SIZE) as f32;
+        push(self.game.score(player) as f32 / cards);
+        push(self.game.score(!player) as f32 / cards);
+        push(self.game.placed_count() as f32 / CELLS as f32);
+
+        obs
+    }
+
+    fn sides(&self, card: CardId) -> [f32; 4] {
+        let stats = &self.card_db.stats[card.index()];
+        [stats.top, stats.rgt, stats.btm, stats.lft].map(|side| side as f32 / 10.0)
+    }
+
+    fn play(&mut self, mv: Move) {
+        self.game.play(mv, self.rules, self.card_db);
+        self.record.moves.push(mv);
+    }
+
+    /// Plays the opponent's turns until the agent is on turn or the match is over.
+    fn play_opponent(&mut self) {
+        let Some((strength, _)) = self.opponent else {
+            return;
+        };
+
+        while self.game.turn != self.learner && !self.game.is_over() {
+            let Some(mv) = self.search.best_move(&self.game, strength, &mut self.rng) else {
+                return;
+            };
+            self.play(mv);
+        }
+    }
+}
+
+fn flag(value: bool) -> f32 {
+    if value { 1.0 } else { 0.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::CardQuery;
+
+    #[test]
+    fn steps_after_the_match_are_done() {
+        let card_db = CardDb::embedded().unwrap();
+        let pool = card_db.pool(&CardQuery::new());
+        let mut env = Env::new(RuleSet::default(), &card_db, &pool)
+            .with_opponent(Strength::Random, Weights::default());
+        env.reset(1);
+
+        let mut done = false;
+        for _ in 0..HAND_SIZE {
+            let action = env.action_mask().iter().position(|&legal| legal).unwrap();
+            (_, _, done) = env.step(action);
+        }
+        assert!(done && env.game().is_over());
+
+        for action in [0, ACTIONS - 1] {
+            let (_, reward, done) = env.step(action);
+            assert_eq!((reward, done), (0.0, true));
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{
+    core::{
+        battle::{HAND_SIZE, Pool},
+        data::CardId,
+    },
+    sys::rand::{Rng, shuffle},
+};
+
+// ====================================== HandInference ============================================
+
+/// What the player can tell about a concealed hand: the cards known to be in it and, for the
+/// remaining slots, the cards they might be.
+///
+/// Hands are drawn from the NPC pool without replacement, so every candidate is equally likely to
+/// fill an unknown slot. Cards are known once played, or when carried over by Sudden Death.
+#[derive(Clone, Debug)]
+pub struct HandInference {
+    /// Cards known to be in the hand.
+    pub known: Vec<CardId>,
+    /// Cards the unknown slots might hold, all equally likely.
+    pub candidates: Vec<CardId>,
+    /// Slots of the hand holding an unknown card.
+    pub unknown: usize,
+}
+
+impl HandInference {
+    pub fn new(pool: &Pool) -> Self {
+        Self {
+            known: Vec::new(),
+            candidates: pool.0.to_vec(),
+            unknown: HAND_SIZE,
+        }
+    }
+
+    pub fn hand_size(&self) -> usize {
+        self.known.len() + self.unknown
+    }
+
+    /// Records `card` being played from the hand.
+    pub fn reveal(&mut self, card: CardId) {
+        if let Some(j) = self.known.iter().position(|&known| known == card) {
+            self.known.swap_remove(j);
+            return;
+        }
+
+        if let Some(j) = self.candidates.iter().position(|&c| c == card) {
+            self.candidates.swap_remove(j);
+        }
+        self.unknown = self.unknown.saturating_sub(1);
+    }
+
+    /// Starts a Sudden Death rematch, where the hand is made of the cards owned at the end of the
+    /// match: the visible ones in `owned`, plus the cards never played which stay unknown.
+    pub fn rematch(&mut self, owned: impl IntoIterator<Item = CardId>) {
+        self.known.extend(owned);
+    }
+
+    /// Returns the chance that `card` is in the hand.
+    pub fn probability(&self, card: CardId) -> f64 {
+        if self.known.contains(&card) {
+            return 1.0;
+        }
+        if self.candidates.is_empty() {
+            return 0.0;
+        }
+
+        // one minus the chance that every unknown slot holds another candidate, drawn without
+        // replacement: C(len - copies, unknown) / C(len, unknown)
+        let len = self.candidates.len();
+        let copies = self.candidates.iter().filter(|&&c| c == card).count();
+        let missed = (0..self.unknown.min(len))
+            .map(|j| len.saturating_sub(copies + j) as f64 / (len - j) as f64)
+            .product::<f64>();
+
+        1.0 - missed
+    }
+
+    /// Returns every possible card with its chance of being in the hand, most likely first.
+    pub fn distribution(&self) -> Vec<(CardId, f64)> {
+        let mut cards: Vec<CardId> = self.known.clone();
+        for &card in &self.candidates {
+            if !cards.contains(&card) {
+                cards.push(card);
+            }
+        }
+
+        let mut distribution: Vec<(CardId, f64)> = cards
+            .into_iter()
+            .map(|card| (card, self.probability(card)))
+            .collect();
+        distribution.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        distribution
+    }
+
+    /// Draws a hand consistent with what is known, e.g. to search a closed position as if open.
+    pub fn sample(&self, rng: &mut Rng) -> Vec<CardId> {
+        let mut hand = self.known.clone();
+
+        let mut candidates = self.candidates.clone();
+        let unknown = self.unknown.min(candidates.len());
+        if unknown < candidates.len() {
+            shuffle(rng, &mut candidates, unknown);
+        }
+        hand.extend_from_slice(&candidates[..unknown]);
+
+        hand
+    }
+}
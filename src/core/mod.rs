@@ -1,4 +1,20 @@
+pub mod ai;
+pub mod analysis;
 pub mod battle;
+pub mod book;
 pub mod card_pools;
 pub mod data;
+pub mod game;
+pub mod gym;
+pub mod inference;
+pub mod npc;
+pub mod personality;
 pub mod player;
+pub mod record;
+pub mod rules;
+pub mod sim;
+pub mod stats;
+pub mod table;
+pub mod tier;
+pub mod tuning;
+pub mod worker;
@@ -0,0 +1,68 @@
+use crate::core::{ai::Strength, rules::RuleSet};
+
+// ============================================ Npc ================================================
+
+/// NPC opponent profile: the pool its hand is drawn from, how well it plays, its play style and
+/// the rules it challenges the player with.
+pub struct Npc {
+    pub name: &'static str,
+    /// Name of a `CardPool`.
+    pub pool: &'static str,
+    pub strength: Strength,
+    /// Name of a `Personality` preset.
+    pub personality: &'static str,
+    pub rules: RuleSet,
+}
+
+impl Npc {
+    /// Looks up a profile by case insensitive name.
+    pub fn find(name: &str) -> Option<&'static Npc> {
+        NPCS.iter().find(|npc| npc.name.eq_ignore_ascii_case(name))
+    }
+}
+
+#[rustfmt::skip]
+pub static NPCS: [Npc; 6] = [
+    Npc {
+        name: "beginner",
+        pool: "beginner",
+        strength: Strength::Random,
+        personality: "balanced",
+        rules: RuleSet { open: true, same: false, plus: false, sudden_death: false },
+    },
+    Npc {
+        name: "novice",
+        pool: "novice",
+        strength: Strength::Lookahead(1),
+        personality: "aggressive",
+        rules: RuleSet { open: true, same: false, plus: false, sudden_death: false },
+    },
+    Npc {
+        name: "apprentice",
+        pool: "apprentice",
+        strength: Strength::Lookahead(2),
+        personality: "combo-seeker",
+        rules: RuleSet { open: true, same: true, plus: false, sudden_death: false },
+    },
+    Npc {
+        name: "journeyman",
+        pool: "journeyman",
+        strength: Strength::Lookahead(3),
+        personality: "combo-seeker",
+        rules: RuleSet { open: true, same: false, plus: true, sudden_death: false },
+    },
+    Npc {
+        name: "master",
+        pool: "master",
+        strength: Strength::Lookahead(4),
+        personality: "tuned",
+        rules: RuleSet { open: false, same: true, plus: true, sudden_death: false },
+    },
+    Npc {
+        name: "grandmaster",
+        pool: "grandmaster",
+        strength: Strength::PERFECT,
+        personality: "balanced",
+        rules: RuleSet { open: false, same: true, plus: true, sudden_death: true },
+    },
+];
@@ -0,0 +1,181 @@
+use std::{
+    fmt,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use crate::core::ai::Weights;
+
+// ======================================== Personality ============================================
+
+/// Named evaluation weights that give an NPC its play style.
+///
+/// Presets are stored one per line as `name,corner,exposed,combo`. Blank lines and lines starting
+/// with `#` are skipped.
+#[derive(Clone, Debug)]
+pub struct Personality {
+    pub name: String,
+    pub weights: Weights,
+}
+
+impl Personality {
+    const FIELD_SEPARATOR: char = ',';
+
+    pub fn load_all(path: impl AsRef<Path>) -> io::Result<Vec<Self>> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut personalities = Vec::new();
+
+        for (j, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let personality = line.parse().map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("personality at line {}: {e:?}", j + 1),
+                )
+            })?;
+            personalities.push(personality);
+        }
+
+        Ok(personalities)
+    }
+
+    /// Saves `personality` to the presets at `path`, below `comment` with each of its lines
+    /// prefixed with `#`. A preset of the same name is replaced in place along with the comment
+    /// lines right above it, otherwise it's appended. Other lines are kept as they are.
+    pub fn save(path: impl AsRef<Path>, comment: &str, personality: &Self) -> io::Result<()> {
+        let path = path.as_ref();
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+        let mut entry: Vec<String> = comment.lines().map(|line| format!("# {line}")).collect();
+        entry.push(personality.to_string());
+
+        let existing = lines.iter().position(|line| {
+            line.parse::<Self>()
+                .is_ok_and(|other| other.name.eq_ignore_ascii_case(&personality.name))
+        });
+        match existing {
+            Some(j) => {
+                let start = lines[..j]
+                    .iter()
+                    .rposition(|line| !line.starts_with('#'))
+                    .map_or(0, |k| k + 1);
+                lines.splice(start..=j, entry);
+            }
+            None => lines.extend(entry),
+        }
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        for line in &lines {
+            writeln!(writer, "{line}")?;
+        }
+        writer.flush()
+    }
+
+    /// Looks up a preset by case insensitive name.
+    pub fn find<'a>(personalities: &'a [Self], name: &str) -> Option<&'a Self> {
+        personalities
+            .iter()
+            .find(|personality| personality.name.eq_ignore_ascii_case(name))
+    }
+}
+
+impl fmt::Display for Personality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Weights {
+            corner,
+            exposed,
+            combo,
+        } = self.weights;
+        let sep = Self::FIELD_SEPARATOR;
+
+        write!(f, "{}{sep}{corner}{sep}{exposed}{sep}{combo}", self.name)
+    }
+}
+
+impl FromStr for Personality {
+    type Err = PersonalityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(Self::FIELD_SEPARATOR).map(str::trim);
+
+        macro_rules! next_i32 {
+            ($err:expr) => {
+                parts
+                    .next()
+                    .ok_or($err)
+                    .and_then(|s| s.parse::<i32>().map_err(|_| $err))
+            };
+        }
+
+        let name = parts
+            .next()
+            .filter(|name| !name.is_empty())
+            .ok_or(PersonalityError::InvalidName)?
+            .to_string();
+
+        let corner = next_i32!(PersonalityError::InvalidCorner)?;
+        let exposed = next_i32!(PersonalityError::InvalidExposed)?;
+        let combo = next_i32!(PersonalityError::InvalidCombo)?;
+
+        if parts.next().is_some() {
+            return Err(PersonalityError::TrailingFields);
+        }
+
+        Ok(Personality {
+            name,
+            weights: Weights {
+                corner,
+                exposed,
+                combo,
+            },
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum PersonalityError {
+    InvalidCombo,
+    InvalidCorner,
+    InvalidExposed,
+    InvalidName,
+    TrailingFields,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_replaces_the_preset_and_its_comment_in_place() {
+        let path = std::env::temp_dir().join("triple-triad-test.personalities");
+        fs::write(
+            &path,
+            "balanced,0,0,0\n# old run\nTuned,1,1,1\ndefensive,10,35,0\n",
+        )
+        .unwrap();
+
+        let tuned: Personality = "tuned,2,15,0".parse().unwrap();
+        Personality::save(&path, "new run", &tuned).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "balanced,0,0,0\n# new run\ntuned,2,15,0\ndefensive,10,35,0\n"
+        );
+
+        let aggressive: Personality = "aggressive,60,0,10".parse().unwrap();
+        Personality::save(&path, "", &aggressive).unwrap();
+        let personalities = Personality::load_all(&path).unwrap();
+        let names: Vec<_> = personalities.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["balanced", "tuned", "defensive", "aggressive"]);
+    }
+}
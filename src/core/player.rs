@@ -2,34 +2,43 @@ use crate::core::data::CardId;
 
 // ========================================= Inventory =============================================
 
-#[derive(Clone, Copy, Debug)]
-pub struct Inventory([u8; CardId::MAX as usize]);
+/// Count of every card owned, indexed by `CardId`.
+#[derive(Clone, Debug)]
+pub struct Inventory(Vec<u8>);
 
 impl Inventory {
-    pub fn new() -> Self {
-        Self([0; CardId::MAX as usize])
+    /// Returns an empty inventory able to hold the `card_count` cards of the database, expansions
+    /// included.
+    pub fn new(card_count: usize) -> Self {
+        Self(vec![0; card_count])
     }
 
+    /// Adds `count` copies of `card_id`, ignored when the card is unknown to the database.
     pub fn add(&mut self, card_id: CardId, count: u8) {
-        let curr = &mut self.0[card_id.index()];
-        *curr = curr.saturating_add(count);
+        if let Some(curr) = self.0.get_mut(card_id.index()) {
+            *curr = curr.saturating_add(count);
+        }
     }
 
     pub fn remove(&mut self, card_id: CardId, count: u8) {
-        let curr = &mut self.0[card_id.index()];
-        *curr = curr.saturating_sub(count);
+        if let Some(curr) = self.0.get_mut(card_id.index()) {
+            *curr = curr.saturating_sub(count);
+        }
     }
 
-    pub fn iter_distinct(&self) -> impl Iterator<Item = CardId> + '_ {
+    /// Returns every card owned with its count.
+    pub fn iter(&self) -> impl Iterator<Item = (CardId, u8)> + '_ {
         self.0
             .iter()
             .enumerate()
-            .filter_map(|(card_id, &count)| (count > 0).then_some(CardId::new_const(card_id as u8)))
+            .filter(|&(_, &count)| count > 0)
+            .map(|(card_id, &count)| (CardId::new_const(card_id as u8), count))
     }
-}
 
-impl Default for Inventory {
-    fn default() -> Self {
-        Self::new()
+    pub fn iter_distinct(&self) -> impl Iterator<Item = CardId> + '_ {
+        self.0
+            .iter()
+            .enumerate()
+            .filter_map(|(card_id, &count)| (count > 0).then_some(CardId::new_const(card_id as u8)))
     }
 }
@@ -0,0 +1,172 @@
+use std::{
+    fmt,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use crate::{
+    core::{
+        battle::{BattleSetup, BoardCoords, HAND_SIZE},
+        data::CardId,
+        game::{Game, Move},
+        rules::RuleSet,
+    },
+    data::CardDb,
+};
+
+// ======================================== MatchRecord ============================================
+
+/// Everything needed to replay a match: rules, starting hands and the moves played.
+///
+/// The text format has one entry per line:
+///
+/// ```txt
+/// rules same,plus
+/// p1 99 100 101 102 103
+/// p2 0 1 2 3 4
+/// move 0 1 1
+/// ```
+///
+/// where a move is the hand index followed by the board `x` and `y`.
+#[derive(Clone, Debug)]
+pub struct MatchRecord {
+    pub rules: RuleSet,
+    pub setup: BattleSetup,
+    pub moves: Vec<Move>,
+}
+
+impl MatchRecord {
+    pub fn new(rules: RuleSet, setup: BattleSetup) -> Self {
+        Self {
+            rules,
+            setup,
+            moves: Vec::new(),
+        }
+    }
+
+    /// Returns the position before each move, followed by the final position, failing on the
+    /// first move that isn't legal in its position.
+    pub fn positions(&self, card_db: &CardDb) -> Result<Vec<Game>, RecordError> {
+        let mut game = Game::new(&self.setup);
+        let mut positions = vec![game];
+
+        for (turn, &mv) in self.moves.iter().enumerate() {
+            if !game.moves().any(|legal| legal == mv) {
+                return Err(RecordError::IllegalMove(turn));
+            }
+
+            game.play(mv, self.rules, card_db);
+            positions.push(game);
+        }
+
+        Ok(positions)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|e: RecordError| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        write!(writer, "{self}")?;
+        writer.flush()
+    }
+}
+
+impl fmt::Display for MatchRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "rules {}", self.rules)?;
+
+        for (tag, hand) in [("p1", self.setup.p1_hand), ("p2", self.setup.p2_hand)] {
+            write!(f, "{tag}")?;
+            for card in hand {
+                write!(f, " {}", card.index())?;
+            }
+            writeln!(f)?;
+        }
+
+        for mv in &self.moves {
+            writeln!(f, "move {} {} {}", mv.hand, mv.cell.x(), mv.cell.y())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for MatchRecord {
+    type Err = RecordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rules = None;
+        let mut p1_hand = None;
+        let mut p2_hand = None;
+        let mut moves = Vec::new();
+
+        for (j, line) in s
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+        {
+            let err = RecordError::InvalidLine(j + 1);
+            let (tag, rest) = line.split_once(' ').ok_or(err)?;
+
+            match tag {
+                "rules" => rules = Some(rest.parse::<RuleSet>().map_err(|_| err)?),
+                "p1" => p1_hand = Some(parse_hand(rest).ok_or(err)?),
+                "p2" => p2_hand = Some(parse_hand(rest).ok_or(err)?),
+                "move" => moves.push(parse_move(rest).ok_or(err)?),
+                _ => return Err(err),
+            }
+        }
+
+        Ok(MatchRecord {
+            rules: rules.ok_or(RecordError::MissingRules)?,
+            setup: BattleSetup {
+                p1_hand: p1_hand.ok_or(RecordError::MissingHand)?,
+                p2_hand: p2_hand.ok_or(RecordError::MissingHand)?,
+            },
+            moves,
+        })
+    }
+}
+
+fn parse_hand(s: &str) -> Option<[CardId; HAND_SIZE]> {
+    let cards: Vec<CardId> = s
+        .split_whitespace()
+        .map(|id| id.parse().ok().and_then(CardId::new))
+        .collect::<Option<_>>()?;
+
+    cards.try_into().ok()
+}
+
+fn parse_move(s: &str) -> Option<Move> {
+    let mut parts = s.split_whitespace().map(|n| n.parse::<usize>().ok());
+    let hand = parts.next()??;
+    let cell = BoardCoords::new(parts.next()??, parts.next()??)?;
+
+    parts.next().is_none().then_some(Move { hand, cell })
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum RecordError {
+    /// The move of the turn, counted from 0, can't be played in its position.
+    IllegalMove(usize),
+    InvalidLine(usize),
+    MissingHand,
+    MissingRules,
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordError::IllegalMove(turn) => write!(f, "illegal move at turn {turn}"),
+            RecordError::InvalidLine(line) => write!(f, "invalid record at line {line}"),
+            RecordError::MissingHand => write!(f, "record is missing a starting hand"),
+            RecordError::MissingRules => write!(f, "record is missing the rules"),
+        }
+    }
+}
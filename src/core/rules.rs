@@ -0,0 +1,313 @@
+use std::{fmt, str::FromStr};
+
+use crate::{
+    core::{
+        battle::{BOARD_SIZE, BoardCoords, Direction, Player},
+        data::CardId,
+    },
+    data::{CardDb, Stats},
+};
+
+// ========================================== RuleSet ==============================================
+
+/// Optional rules on top of the basic capture rule.
+///
+/// Combo is implied: cards flipped by `same` or `plus` capture their own neighbors in turn.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct RuleSet {
+    /// Both hands are visible.
+    pub open: bool,
+    /// Two or more sides matching the facing values capture the matched cards.
+    pub same: bool,
+    /// Two or more sides with equal sums against the facing values capture the matched cards.
+    pub plus: bool,
+    /// A draw is replayed, each player keeping the cards they owned at the end.
+    pub sudden_death: bool,
+}
+
+impl RuleSet {
+    const SEPARATOR: char = ',';
+}
+
+impl fmt::Display for RuleSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = [
+            (self.open, "open"),
+            (self.same, "same"),
+            (self.plus, "plus"),
+            (self.sudden_death, "sudden-death"),
+        ];
+
+        let mut first = true;
+        for (_, name) in names.iter().filter(|(enabled, _)| *enabled) {
+            if !first {
+                write!(f, "{}", Self::SEPARATOR)?;
+            }
+            write!(f, "{name}")?;
+            first = false;
+        }
+
+        if first {
+            write!(f, "basic")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for RuleSet {
+    type Err = RuleError;
+
+    /// Parses a comma separated list of rule names, e.g. `open,same,plus`. `basic` alone yields
+    /// the default rule set.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rules = RuleSet::default();
+
+        for name in s.split(Self::SEPARATOR).map(str::trim) {
+            match name {
+                "basic" => {}
+                "open" => rules.open = true,
+                "same" => rules.same = true,
+                "plus" => rules.plus = true,
+                "sudden-death" => rules.sudden_death = true,
+                _ => return Err(RuleError::UnknownRule(name.to_string())),
+            }
+        }
+
+        Ok(rules)
+    }
+}
+
+#[derive(Debug)]
+pub enum RuleError {
+    UnknownRule(String),
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleError::UnknownRule(name) => write!(f, "unknown rule: '{name}'"),
+        }
+    }
+}
+
+// =========================================== Grid ================================================
+
+/// A card placed on the board.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cell {
+    pub card: CardId,
+    pub owner: Player,
+}
+
+/// Board contents indexed by `BoardCoords::index`.
+pub type Grid = [Option<Cell>; BOARD_SIZE * BOARD_SIZE];
+
+/// Set of board cells, one bit per `BoardCoords::index`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Flips(u16);
+
+impl Flips {
+    pub fn contains(self, coords: BoardCoords) -> bool {
+        self.0 & (1 << coords.index()) != 0
+    }
+
+    pub fn insert(&mut self, coords: BoardCoords) {
+        self.0 |= 1 << coords.index();
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn iter(self) -> impl Iterator<Item = BoardCoords> {
+        (0..BOARD_SIZE * BOARD_SIZE)
+            .filter(move |j| self.0 & (1 << j) != 0)
+            .filter_map(|j| BoardCoords::new(j % BOARD_SIZE, j / BOARD_SIZE))
+    }
+}
+
+// ========================================= Captures ==============================================
+
+/// Returns the cells captured by the card just placed at `placed`.
+///
+/// `grid` must already contain the placed card. The grid itself is left untouched, callers apply
+/// the returned flips.
+pub fn captures(grid: &Grid, placed: BoardCoords, rules: RuleSet, card_db: &CardDb) -> Flips {
+    let mut grid = *grid;
+    let mut flips = Flips::default();
+
+    let Some(attacker) = grid[placed.index()] else {
+        return flips;
+    };
+
+    // Same and Plus only trigger on placement, then chain through Combo
+    let mut combo: Vec<BoardCoords> = Vec::new();
+    if rules.same || rules.plus {
+        let neighbors = Direction::ALL.map(|dir| {
+            let coords = placed.neighbor(dir)?;
+            let cell = grid[coords.index()]?;
+            let atk = side(&card_db.stats[attacker.card.index()], dir);
+            let def = side(&card_db.stats[cell.card.index()], dir.opposite());
+            Some((coords, cell, atk, def))
+        });
+
+        let mut special = |matches: &dyn Fn(u8, u8) -> bool| {
+            let hits = || {
+                neighbors
+                    .iter()
+                    .flatten()
+                    .filter(|(_, _, atk, def)| matches(*atk, *def))
+            };
+
+            if hits().count() < 2 {
+                return;
+            }
+
+            for (coords, cell, ..) in hits() {
+                if cell.owner != attacker.owner && !flips.contains(*coords) {
+                    flips.insert(*coords);
+                    combo.push(*coords);
+                }
+            }
+        };
+
+        if rules.same {
+            special(&|atk, def| atk == def);
+        }
+
+        if rules.plus {
+            for (_, _, atk, def) in neighbors.iter().flatten() {
+                let sum = atk + def;
+                special(&|a, d| a + d == sum);
+            }
+        }
+    }
+
+    for coords in &combo {
+        if let Some(cell) = grid[coords.index()].as_mut() {
+            cell.owner = attacker.owner;
+        }
+    }
+
+    // basic captures are taken before Combo, which they don't start
+    let captured = basic(&grid, placed, card_db);
+    for coords in captured.iter() {
+        if let Some(cell) = grid[coords.index()].as_mut() {
+            cell.owner = attacker.owner;
+        }
+    }
+    flips = flips.union(captured);
+
+    // Combo: every card flipped by Same or Plus attacks its neighbors with the basic rule
+    while let Some(coords) = combo.pop() {
+        for target in basic(&grid, coords, card_db).iter() {
+            if flips.contains(target) {
+                continue;
+            }
+            if let Some(cell) = grid[target.index()].as_mut() {
+                cell.owner = attacker.owner;
+            }
+            flips.insert(target);
+            combo.push(target);
+        }
+    }
+
+    flips
+}
+
+/// Returns the opponent cells that the card at `from` beats on the facing side.
+fn basic(grid: &Grid, from: BoardCoords, card_db: &CardDb) -> Flips {
+    let mut flips = Flips::default();
+
+    let Some(attacker) = grid[from.index()] else {
+        return flips;
+    };
+
+    for dir in Direction::ALL {
+        let Some(coords) = from.neighbor(dir) else {
+            continue;
+        };
+        let Some(defender) = grid[coords.index()] else {
+            continue;
+        };
+        if defender.owner == attacker.owner {
+            continue;
+        }
+
+        let atk = side(&card_db.stats[attacker.card.index()], dir);
+        let def = side(&card_db.stats[defender.card.index()], dir.opposite());
+        if atk > def {
+            flips.insert(coords);
+        }
+    }
+
+    flips
+}
+
+/// Returns the stat facing `dir`.
+pub fn side(stats: &Stats, dir: Direction) -> u8 {
+    match dir {
+        Direction::Down => stats.btm,
+        Direction::Left => stats.lft,
+        Direction::Right => stats.rgt,
+        Direction::Up => stats.top,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Card;
+
+    #[test]
+    fn basic_captures_dont_start_combos() {
+        // top, right, bottom, left
+        let card_db: CardDb = [
+            "1,Placed,5,9,1,5,None",
+            "1,SameTop,1,9,5,1,None",
+            "1,SameLeft,1,5,1,1,None",
+            "1,Combo,1,1,9,1,None",
+            "1,Basic,1,1,9,1,None",
+            "1,Beyond,1,1,1,1,None",
+        ]
+        .iter()
+        .map(|card| card.parse::<Card>().unwrap())
+        .collect();
+
+        let at = |x, y| BoardCoords::new(x, y).unwrap();
+        let mut grid: Grid = [None; BOARD_SIZE * BOARD_SIZE];
+        for (j, (x, y), owner) in [
+            (0, (1, 1), Player::P1),
+            (1, (1, 0), Player::P2),
+            (2, (0, 1), Player::P2),
+            (3, (2, 0), Player::P2),
+            (4, (2, 1), Player::P2),
+            (5, (2, 2), Player::P2),
+        ] {
+            let card = CardId::new(j).unwrap();
+            grid[at(x, y).index()] = Some(Cell { card, owner });
+        }
+
+        let rules = RuleSet {
+            same: true,
+            ..Default::default()
+        };
+        let flips = captures(&grid, at(1, 1), rules, &card_db);
+
+        // the Same flips chain into `Combo`, which can't take `Basic` again to reach `Beyond`
+        let mut expected = Flips::default();
+        for (x, y) in [(1, 0), (0, 1), (2, 0), (2, 1)] {
+            expected.insert(at(x, y));
+        }
+        assert_eq!(flips, expected);
+    }
+}
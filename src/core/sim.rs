@@ -0,0 +1,417 @@
+use std::{
+    panic,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use crate::{
+    core::{
+        ai::{Search, Strength, Weights},
+        battle::{BOARD_SIZE, BattleResult, BattleSetup, BoardCoords, HAND_SIZE, Player, Pool},
+        data::CardId,
+        game::Game,
+        record::MatchRecord,
+        rules::{Flips, RuleSet},
+    },
+    data::CardDb,
+    sys::rand::Rng,
+};
+
+// =========================================== Seat ================================================
+
+/// AI controlled contestant of a headless match.
+pub struct Seat<'a> {
+    pub strength: Strength,
+    pub weights: Weights,
+    pub deck: Deck<'a>,
+}
+
+/// Where a seat's starting hand comes from.
+pub enum Deck<'a> {
+    /// A new hand is drawn for every deal.
+    Pool(&'a Pool<'a>),
+    /// The same hand is played every time.
+    Fixed([CardId; HAND_SIZE]),
+}
+
+impl Deck<'_> {
+    pub fn draw(&self, rng: &mut Rng) -> [CardId; HAND_SIZE] {
+        match self {
+            Deck::Pool(pool) => pool.draw_hand(rng),
+            Deck::Fixed(hand) => *hand,
+        }
+    }
+}
+
+// ======================================== Simulation =============================================
+
+/// Batch of headless AI vs AI matches.
+///
+/// Hands are dealt in pairs of matches with the same hands, the seats taking turns at moving
+/// first, so that first player advantage does not favor either seat. Every deal is seeded on its
+/// own and every match starts from empty tables: results only depend on the seed, not on the
+/// number of threads.
+///
+/// Sudden Death is not replayed, drawn matches count as draws.
+pub struct Simulation<'a> {
+    pub rules: RuleSet,
+    pub card_db: &'a CardDb,
+    pub seats: [Seat<'a>; 2],
+    pub threads: usize,
+}
+
+/// Result of a single simulated match.
+#[derive(Clone, Debug)]
+pub struct Outcome {
+    pub record: MatchRecord,
+    /// Seat playing as `Player::P1`, which moves first.
+    pub first: usize,
+    pub result: BattleResult,
+}
+
+impl Outcome {
+    /// Returns the seat playing as `player`.
+    pub fn seat(&self, player: Player) -> usize {
+        match player {
+            Player::P1 => self.first,
+            Player::P2 => 1 - self.first,
+        }
+    }
+
+    /// Returns the winning seat, if any.
+    pub fn winner(&self) -> Option<usize> {
+        match self.result {
+            BattleResult::Draw => None,
+            BattleResult::Win(player) => Some(self.seat(player)),
+        }
+    }
+
+    /// Returns the starting hand of `seat`.
+    pub fn hand(&self, seat: usize) -> [CardId; HAND_SIZE] {
+        if seat == self.first {
+            self.record.setup.p1_hand
+        } else {
+            self.record.setup.p2_hand
+        }
+    }
+}
+
+impl<'a> Simulation<'a> {
+    pub fn new(rules: RuleSet, card_db: &'a CardDb, seats: [Seat<'a>; 2]) -> Self {
+        Self {
+            rules,
+            card_db,
+            seats,
+            threads: 1,
+        }
+    }
+
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Plays `matches` matches and returns their outcomes in order.
+    pub fn run(&self, matches: usize, seed: u64) -> Vec<Outcome> {
+        let next = AtomicUsize::new(0);
+
+        let worker = || {
+            // each seat keeps its own search, as the table depends on the weights
+            let searches = self
+                .seats
+                .each_ref()
+                .map(|seat| Search::new(self.rules, self.card_db, seat.weights));
+
+            let mut outcomes = Vec::new();
+            loop {
+                let j = next.fetch_add(1, Ordering::Relaxed);
+                if j >= matches {
+                    break;
+                }
+                outcomes.push((j, self.play(j, seed, &searches)));
+            }
+
+            outcomes
+        };
+
+        let mut outcomes: Vec<(usize, Outcome)> = if self.threads == 1 {
+            worker()
+        } else {
+            thread::scope(|scope| {
+                let handles: Vec<_> = (0..self.threads.min(matches))
+                    .map(|_| scope.spawn(worker))
+                    .collect();
+
+                handles
+                    .into_iter()
+                    .flat_map(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+                    .collect()
+            })
+        };
+
+        outcomes.sort_by_key(|(j, _)| *j);
+        outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+    }
+
+    fn play(&self, j: usize, seed: u64, searches: &[Search; 2]) -> Outcome {
+        // what a worker searched in its earlier matches must not sway this one
+        for search in searches {
+            search.clear_table();
+        }
+
+        let deal = (j / 2) as u64;
+        let first = j % 2;
+
+        let mut rng = Rng::from_seed(seed.wrapping_add(deal));
+        let hands = self.seats.each_ref().map(|seat| seat.deck.draw(&mut rng));
+
+        let setup = BattleSetup {
+            p1_hand: hands[first],
+            p2_hand: hands[1 - first],
+        };
+        let mut record = MatchRecord::new(self.rules, setup);
+        let mut game = Game::new(&setup);
+
+        let result = loop {
+            if let Some(result) = game.result() {
+                break result;
+            }
+
+            let seat = match game.turn {
+                Player::P1 => first,
+                Player::P2 => 1 - first,
+            };
+            let Some(mv) = searches[seat].best_move(&game, self.seats[seat].strength, &mut rng)
+            else {
+                break BattleResult::Draw;
+            };
+
+            game.play(mv, self.rules, self.card_db);
+            record.moves.push(mv);
+        };
+
+        Outcome {
+            record,
+            first,
+            result,
+        }
+    }
+}
+
+// =========================================== Tally ===============================================
+
+/// Aggregated results of a simulation, from the seats' and the cards' points of view.
+#[derive(Clone, Debug, Default)]
+pub struct Tally {
+    pub matches: usize,
+    pub draws: usize,
+    pub wins: [usize; 2],
+    /// Wins of the seat moving first.
+    pub first_wins: usize,
+    /// Per card results, indexed by `CardId::index`.
+    pub cards: Vec<CardTally>,
+}
+
+/// Results of the matches a card started in, and how it fared on the board.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CardTally {
+    pub matches: usize,
+    pub wins: usize,
+    pub draws: usize,
+    /// Sum of the overall score of the seats holding the card, over its matches.
+    pub expected: f64,
+    /// Times the card was placed on the board.
+    pub placed: usize,
+    /// Cards flipped by placing it, combos included.
+    pub captures: usize,
+    /// Times it was placed and never flipped until the end of the match.
+    pub survived: usize,
+}
+
+impl CardTally {
+    pub fn losses(&self) -> usize {
+        self.matches - self.wins - self.draws
+    }
+
+    /// Points per match, a draw being worth half a win, from 0 to 1.
+    pub fn score(&self) -> f64 {
+        if self.matches == 0 {
+            return 0.0;
+        }
+        (self.wins as f64 + self.draws as f64 / 2.0) / self.matches as f64
+    }
+
+    /// Average captures per placement.
+    pub fn capture_rate(&self) -> f64 {
+        rate(self.captures, self.placed)
+    }
+
+    /// Share of placements never flipped.
+    pub fn survival_rate(&self) -> f64 {
+        rate(self.survived, self.placed)
+    }
+
+    /// Score above what the seats holding the card achieve overall, negative if below.
+    pub fn contribution(&self) -> f64 {
+        if self.matches == 0 {
+            return 0.0;
+        }
+        self.score() - self.expected / self.matches as f64
+    }
+}
+
+impl Tally {
+    pub fn new(outcomes: &[Outcome], card_db: &CardDb) -> Self {
+        let mut tally = Tally {
+            cards: vec![CardTally::default(); card_db.len()],
+            ..Tally::default()
+        };
+
+        for outcome in outcomes {
+            tally.matches += 1;
+
+            match outcome.winner() {
+                Some(seat) => {
+                    tally.wins[seat] += 1;
+                    if seat == outcome.first {
+                        tally.first_wins += 1;
+                    }
+                }
+                None => tally.draws += 1,
+            }
+        }
+
+        let scores = [0, 1].map(|seat| tally.score(seat));
+        for outcome in outcomes {
+            for (seat, score) in scores.into_iter().enumerate() {
+                for card in outcome.hand(seat) {
+                    let card_tally = &mut tally.cards[card.index()];
+                    card_tally.matches += 1;
+                    match outcome.winner() {
+                        Some(winner) if winner == seat => card_tally.wins += 1,
+                        None => card_tally.draws += 1,
+                        _ => {}
+                    }
+                    card_tally.expected += score;
+                }
+            }
+
+            tally.replay(&outcome.record, card_db);
+        }
+
+        tally
+    }
+
+    /// Collects board statistics of the cards played in `record`.
+    fn replay(&mut self, record: &MatchRecord, card_db: &CardDb) {
+        let mut game = Game::new(&record.setup);
+        let mut flipped = Flips::default();
+
+        for &mv in &record.moves {
+            let flips = game.play(mv, record.rules, card_db);
+            flipped = flipped.union(flips);
+
+            if let Some(cell) = game.grid[mv.cell.index()] {
+                let card_tally = &mut self.cards[cell.card.index()];
+                card_tally.placed += 1;
+                card_tally.captures += flips.len();
+            }
+        }
+
+        for (j, cell) in game.grid.iter().enumerate() {
+            let Some(cell) = cell else {
+                continue;
+            };
+            let Some(coords) = BoardCoords::new(j % BOARD_SIZE, j / BOARD_SIZE) else {
+                continue;
+            };
+            if !flipped.contains(coords) {
+                self.cards[cell.card.index()].survived += 1;
+            }
+        }
+    }
+
+    /// Score of the hands holding `card` minus the score of the hands without it.
+    pub fn win_delta(&self, card: CardId) -> f64 {
+        let card = &self.cards[card.index()];
+
+        // every match hands out one point between its two hands
+        let hands = 2 * self.matches;
+        let without = hands - card.matches;
+        if card.matches == 0 || without == 0 {
+            return 0.0;
+        }
+
+        let points = card.score() * card.matches as f64;
+        card.score() - (self.matches as f64 - points) / without as f64
+    }
+
+    /// Share of matches ending in a draw.
+    pub fn draw_rate(&self) -> f64 {
+        rate(self.draws, self.matches)
+    }
+
+    pub fn win_rate(&self, seat: usize) -> f64 {
+        rate(self.wins[seat], self.matches)
+    }
+
+    /// Points per match of `seat`, a draw being worth half a win.
+    pub fn score(&self, seat: usize) -> f64 {
+        self.win_rate(seat) + self.draw_rate() / 2.0
+    }
+
+    /// Win rate of the seat moving first minus the win rate of the seat moving second.
+    pub fn first_player_advantage(&self) -> f64 {
+        let second_wins = self.matches - self.draws - self.first_wins;
+        rate(self.first_wins, self.matches) - rate(second_wins, self.matches)
+    }
+}
+
+fn rate(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    count as f64 / total as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{card_pools::CardPool, npc::Npc};
+
+    #[test]
+    fn outcomes_dont_depend_on_the_number_of_threads() {
+        let card_db = CardDb::embedded().unwrap();
+        let npc = Npc::find("grandmaster").unwrap();
+        let pools = CardPool::load_all("config/pools").unwrap();
+        let pool = CardPool::resolve_named(&pools, npc.pool, &card_db).unwrap();
+
+        let seat = |weights| Seat {
+            strength: Strength::Lookahead(4),
+            weights,
+            deck: Deck::Pool(&pool),
+        };
+        let simulation = Simulation::new(
+            npc.rules,
+            &card_db,
+            [
+                seat(Weights::default()),
+                seat(Weights {
+                    corner: 20,
+                    exposed: 10,
+                    combo: 0,
+                }),
+            ],
+        );
+
+        let serial = simulation.run(16, 3);
+        let parallel = simulation.with_threads(4).run(16, 3);
+
+        assert_eq!(serial.len(), parallel.len());
+        for (serial, parallel) in serial.iter().zip(&parallel) {
+            assert_eq!(serial.record.setup.p1_hand, parallel.record.setup.p1_hand);
+            assert_eq!(serial.record.moves, parallel.record.moves);
+            assert_eq!(serial.winner(), parallel.winner());
+        }
+    }
+}
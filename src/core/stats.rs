@@ -0,0 +1,42 @@
+use std::fmt;
+
+use crate::core::battle::Player;
+
+// ======================================== MatchStats =============================================
+
+/// Per player counters collected over a match.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MatchStats {
+    pub placed: [u8; 2],
+    pub captured: [u8; 2],
+    pub hints: [u16; 2],
+}
+
+impl MatchStats {
+    pub fn record_placement(&mut self, player: Player) {
+        self.placed[player as usize] += 1;
+    }
+
+    pub fn record_capture(&mut self, player: Player) {
+        self.captured[player as usize] += 1;
+    }
+
+    pub fn record_hint(&mut self, player: Player) {
+        self.hints[player as usize] += 1;
+    }
+}
+
+impl fmt::Display for MatchStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for player in [Player::P1, Player::P2] {
+            let j = player as usize;
+            writeln!(
+                f,
+                "{player:?}: placed {}, captured {}, hints {}",
+                self.placed[j], self.captured[j], self.hints[j]
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,183 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{
+    core::{
+        battle::{BOARD_SIZE, Player},
+        game::Game,
+    },
+    sys::rand::Rng,
+};
+
+// ========================================== Zobrist ==============================================
+
+/// Position hashing keys.
+///
+/// Hand cards are summed rather than xored so that duplicated cards do not cancel out, and hand
+/// order is ignored as it does not change the value of a position.
+pub struct Zobrist {
+    cells: Vec<u64>,
+    hands: Vec<u64>,
+    turn: u64,
+}
+
+impl Zobrist {
+    const SEED: u64 = 0x7472_6970_6c65_7464;
+
+    /// One key per possible card index, independent of `CardId::MAX`, so that hashes and the
+    /// opening book keyed by them don't change with the number of cards allowed.
+    const CARDS: usize = u8::MAX as usize + 1;
+
+    pub fn new() -> Self {
+        let mut rng = Rng::from_seed(Self::SEED);
+        let cells = BOARD_SIZE * BOARD_SIZE * 2 * Self::CARDS;
+
+        Self {
+            cells: (0..cells).map(|_| rng.u64()).collect(),
+            hands: (0..2 * Self::CARDS).map(|_| rng.u64()).collect(),
+            turn: rng.u64(),
+        }
+    }
+
+    /// Returns a hash of the keys, which changes with the layout of the key tables.
+    pub fn fingerprint(&self) -> u64 {
+        self.cells
+            .iter()
+            .chain(&self.hands)
+            .chain([&self.turn])
+            .fold(0, |hash, &key| hash.rotate_left(7) ^ key)
+    }
+
+    pub fn hash(&self, game: &Game) -> u64 {
+        self.hash_board(game)
+            .wrapping_add(self.hash_hand(game, Player::P1))
+            .wrapping_add(self.hash_hand(game, Player::P2))
+    }
+
+    /// Hashes what the player on turn sees of `game` for sure: the board and their own hand,
+    /// leaving out the opponent's.
+    pub fn hash_mover(&self, game: &Game) -> u64 {
+        self.hash_board(game)
+            .wrapping_add(self.hash_hand(game, game.turn))
+    }
+
+    /// Hashes the turn and the cards placed.
+    fn hash_board(&self, game: &Game) -> u64 {
+        let mut hash = match game.turn {
+            Player::P1 => 0,
+            Player::P2 => self.turn,
+        };
+
+        for (j, cell) in game.grid.iter().enumerate() {
+            if let Some(cell) = cell {
+                let key = (j * 2 + cell.owner as usize) * Self::CARDS + cell.card.index();
+                hash = hash.wrapping_add(self.cells[key]);
+            }
+        }
+
+        hash
+    }
+
+    fn hash_hand(&self, game: &Game, player: Player) -> u64 {
+        game.hand(player)
+            .map(|card| self.hands[player as usize * Self::CARDS + card.index()])
+            .fold(0, u64::wrapping_add)
+    }
+}
+
+impl Default for Zobrist {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ==================================== TranspositionTable =========================================
+
+/// Kind of value stored by an alpha-beta search.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum Bound {
+    Exact = 0,
+    Lower = 1,
+    Upper = 2,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Entry {
+    pub value: i32,
+    pub depth: u8,
+    pub bound: Bound,
+}
+
+/// Fixed size, always replace table shared between search threads.
+///
+/// Entries are two atomics, the key being stored xored with the data: a torn write fails the key
+/// check and reads as a miss, so no locking is needed.
+pub struct TranspositionTable {
+    slots: Vec<[AtomicU64; 2]>,
+}
+
+impl TranspositionTable {
+    /// Number of slots of the default table, 16 bytes each.
+    pub const DEFAULT_SLOTS: usize = 1 << 18;
+
+    pub fn new(slots: usize) -> Self {
+        let slots = slots.max(1).next_power_of_two();
+
+        Self {
+            slots: (0..slots)
+                .map(|_| [AtomicU64::new(0), AtomicU64::new(0)])
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, hash: u64) -> Option<Entry> {
+        let [key, data] = self.slot(hash);
+        let data = data.load(Ordering::Relaxed);
+
+        if key.load(Ordering::Relaxed) ^ data != hash || data == 0 {
+            return None;
+        }
+
+        let bound = match (data >> 40) as u8 {
+            0 => Bound::Exact,
+            1 => Bound::Lower,
+            2 => Bound::Upper,
+            _ => return None,
+        };
+
+        Some(Entry {
+            value: data as u32 as i32,
+            depth: (data >> 32) as u8,
+            bound,
+        })
+    }
+
+    pub fn insert(&self, hash: u64, entry: Entry) {
+        // the marker bit keeps a stored entry distinct from an empty slot
+        let data = (entry.value as u32 as u64)
+            | ((entry.depth as u64) << 32)
+            | ((entry.bound as u64) << 40)
+            | (1 << 48);
+
+        let [key, slot] = self.slot(hash);
+        key.store(hash ^ data, Ordering::Relaxed);
+        slot.store(data, Ordering::Relaxed);
+    }
+
+    pub fn clear(&self) {
+        for [key, data] in &self.slots {
+            key.store(0, Ordering::Relaxed);
+            data.store(0, Ordering::Relaxed);
+        }
+    }
+
+    fn slot(&self, hash: u64) -> &[AtomicU64; 2] {
+        &self.slots[hash as usize & (self.slots.len() - 1)]
+    }
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SLOTS)
+    }
+}
@@ -0,0 +1,59 @@
+use crate::core::{data::CardId, sim::Tally};
+
+// ========================================= TierList ==============================================
+
+/// Tiers from best to worst.
+pub const TIERS: [char; 5] = ['S', 'A', 'B', 'C', 'D'];
+
+/// Cards of a level band ranked by simulated value, best first.
+///
+/// Cards are ranked on their win rate delta, and split into `TIERS` of about equal size: tiers
+/// compare cards within the band, not across levels.
+#[derive(Clone, Debug)]
+pub struct TierList {
+    pub level: u8,
+    pub entries: Vec<TierEntry>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TierEntry {
+    pub card: CardId,
+    pub tier: char,
+    /// Matches the card started in.
+    pub matches: usize,
+    /// Score of the hands holding the card minus the score of the hands without it.
+    pub win_delta: f64,
+    /// Average captures per placement.
+    pub capture_rate: f64,
+    /// Share of placements never flipped.
+    pub survival_rate: f64,
+}
+
+impl TierList {
+    /// Ranks `cards` from the tally of a simulation between hands drawn from them.
+    pub fn new(level: u8, cards: &[CardId], tally: &Tally) -> Self {
+        let mut entries: Vec<TierEntry> = cards
+            .iter()
+            .map(|&card| {
+                let card_tally = &tally.cards[card.index()];
+                TierEntry {
+                    card,
+                    tier: TIERS[TIERS.len() - 1],
+                    matches: card_tally.matches,
+                    win_delta: tally.win_delta(card),
+                    capture_rate: card_tally.capture_rate(),
+                    survival_rate: card_tally.survival_rate(),
+                }
+            })
+            .collect();
+
+        entries.sort_by(|a, b| b.win_delta.total_cmp(&a.win_delta));
+
+        let len = entries.len();
+        for (j, entry) in entries.iter_mut().enumerate() {
+            entry.tier = TIERS[j * TIERS.len() / len];
+        }
+
+        Self { level, entries }
+    }
+}
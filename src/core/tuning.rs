@@ -0,0 +1,145 @@
+use crate::{
+    core::{
+        ai::{Strength, Weights},
+        battle::Pool,
+        rules::RuleSet,
+        sim::{Deck, Seat, Simulation, Tally},
+    },
+    data::CardDb,
+    sys::rand::Rng,
+};
+
+// ========================================== Tuner ================================================
+
+/// Hill climbing of evaluation weights through self-play against fixed baseline weights.
+///
+/// Every candidate plays the same deals, seeded once from the tuner's seed, so that candidates are
+/// compared on equal terms and a run is reproducible from its seed.
+pub struct Tuner<'a> {
+    pub rules: RuleSet,
+    pub card_db: &'a CardDb,
+    pub pool: &'a Pool<'a>,
+    pub strength: Strength,
+    pub baseline: Weights,
+    /// Matches played per evaluation.
+    pub matches: usize,
+    /// Largest change of a single weight per step.
+    pub step: i32,
+    pub threads: usize,
+    /// Best weights found so far and their score against the baseline, once evaluated.
+    pub best: (Weights, f64),
+    rng: Rng,
+    deals: u64,
+}
+
+/// Result of a single tuning step.
+#[derive(Clone, Copy, Debug)]
+pub struct Step {
+    pub candidate: Weights,
+    pub score: f64,
+    pub accepted: bool,
+}
+
+impl<'a> Tuner<'a> {
+    /// Weights are kept within `0..=MAX_WEIGHT`, a card's worth.
+    pub const MAX_WEIGHT: i32 = Weights::CARD;
+
+    pub fn new(
+        rules: RuleSet,
+        card_db: &'a CardDb,
+        pool: &'a Pool<'a>,
+        strength: Strength,
+        seed: u64,
+    ) -> Self {
+        let mut rng = Rng::from_seed(seed);
+        let deals = rng.u64();
+
+        Self {
+            rules,
+            card_db,
+            pool,
+            strength,
+            baseline: Weights::default(),
+            matches: 200,
+            step: 20,
+            threads: 1,
+            best: (Weights::default(), f64::NEG_INFINITY),
+            rng,
+            deals,
+        }
+    }
+
+    pub fn with_baseline(mut self, baseline: Weights) -> Self {
+        self.baseline = baseline;
+        self
+    }
+
+    /// Starts climbing from `weights` rather than all zero weights.
+    pub fn with_start(mut self, weights: Weights) -> Self {
+        self.best = (weights, f64::NEG_INFINITY);
+        self
+    }
+
+    pub fn with_matches(mut self, matches: usize) -> Self {
+        self.matches = matches.max(1);
+        self
+    }
+
+    pub fn with_step(mut self, step: i32) -> Self {
+        self.step = step.max(1);
+        self
+    }
+
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Returns the score of `weights` against the baseline, from 0 to 1.
+    pub fn evaluate(&self, weights: Weights) -> f64 {
+        let seats = [weights, self.baseline].map(|weights| Seat {
+            strength: self.strength,
+            weights,
+            deck: Deck::Pool(self.pool),
+        });
+
+        let outcomes = Simulation::new(self.rules, self.card_db, seats)
+            .with_threads(self.threads)
+            .run(self.matches, self.deals);
+
+        Tally::new(&outcomes, self.card_db).score(0)
+    }
+
+    /// Evaluates a random neighbor of the best weights, keeping it if it scores higher.
+    pub fn step(&mut self) -> Step {
+        if self.best.1 == f64::NEG_INFINITY {
+            self.best.1 = self.evaluate(self.best.0);
+        }
+        let (best, best_score) = self.best;
+
+        let candidate = Weights {
+            corner: self.perturb(best.corner),
+            exposed: self.perturb(best.exposed),
+            combo: self.perturb(best.combo),
+        };
+
+        let score = self.evaluate(candidate);
+        let accepted = score > best_score;
+        if accepted {
+            self.best = (candidate, score);
+        }
+
+        Step {
+            candidate,
+            score,
+            accepted,
+        }
+    }
+
+    fn perturb(&mut self, weight: i32) -> i32 {
+        let span = (2 * self.step + 1).clamp(1, u8::MAX as i32) as u8;
+        let delta = self.rng.u8_in(0..span) as i32 - (span / 2) as i32;
+
+        (weight + delta).clamp(0, Self::MAX_WEIGHT)
+    }
+}
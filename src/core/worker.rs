@@ -0,0 +1,121 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender, TryRecvError},
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    core::{
+        ai::{Search, Strength, Weights, available_threads},
+        book::OpeningBook,
+        game::{Game, Move},
+        rules::RuleSet,
+    },
+    data::CardDb,
+    sys::rand::Rng,
+};
+
+// ========================================== AiWorker =============================================
+
+/// Background thread computing AI moves, so that searching never blocks the main loop.
+///
+/// Dropping the worker cancels the search in progress and joins the thread.
+pub struct AiWorker {
+    requests: Option<Sender<Request>>,
+    replies: Receiver<Option<Move>>,
+    cancel: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+pub struct Request {
+    pub game: Game,
+    pub rules: RuleSet,
+    pub strength: Strength,
+    pub weights: Weights,
+    pub budget: Duration,
+    pub book: Option<Arc<OpeningBook>>,
+    pub seed: u64,
+}
+
+impl AiWorker {
+    pub fn spawn(card_db: Arc<CardDb>) -> Self {
+        let (requests, inbox) = mpsc::channel::<Request>();
+        let (outbox, replies) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        let handle = thread::spawn({
+            let cancel = cancel.clone();
+            move || {
+                // kept across requests so that the table carries over from move to move
+                let mut search: Option<Search> = None;
+
+                while let Ok(request) = inbox.recv() {
+                    let search = match &mut search {
+                        Some(search)
+                            if search.rules == request.rules
+                                && search.weights == request.weights =>
+                        {
+                            search
+                        }
+                        _ => search.insert(
+                            Search::new(request.rules, &card_db, request.weights)
+                                .with_threads(available_threads())
+                                .with_cancel(cancel.clone()),
+                        ),
+                    };
+                    search.set_deadline(Instant::now() + request.budget);
+                    search.book = request.book;
+
+                    let mut rng = Rng::from_seed(request.seed);
+                    let mv = search.best_move(&request.game, request.strength, &mut rng);
+
+                    if cancel.load(Ordering::Relaxed) || outbox.send(mv).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        Self {
+            requests: Some(requests),
+            replies,
+            cancel,
+            handle: Some(handle),
+        }
+    }
+
+    pub fn request(&self, request: Request) {
+        if let Some(requests) = &self.requests
+            && requests.send(request).is_err()
+        {
+            eprintln!("ERR: AI worker is gone");
+        }
+    }
+
+    /// Returns the reply to the oldest pending request, if it's ready.
+    pub fn poll(&self) -> Option<Option<Move>> {
+        match self.replies.try_recv() {
+            Ok(mv) => Some(mv),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(None),
+        }
+    }
+}
+
+impl Drop for AiWorker {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+        // closing the channel ends the worker loop
+        self.requests.take();
+
+        if let Some(handle) = self.handle.take()
+            && handle.join().is_err()
+        {
+            eprintln!("ERR: AI worker panicked");
+        }
+    }
+}
@@ -0,0 +1,854 @@
+use std::{
+    fmt,
+    io::{self, Write},
+    ops::RangeInclusive,
+    str::FromStr,
+};
+
+/// Cards of the base set.
+pub const CARD_COUNT: usize = 110;
+
+/// Cards a database can hold, as many as `CardId` can index.
+pub const MAX_CARDS: usize = u8::MAX as usize;
+
+pub const MAGIC: [u8; 4] = *b"TTDB";
+pub const VERSION: u8 = 2;
+
+/// Writes `cards` as a database in the current format version: the `MAGIC` bytes, the version, the
+/// card count and the checksum of the records, little endian, followed by the records.
+pub fn write_db<W: Write>(cards: &[Card], writer: &mut W) -> io::Result<()> {
+    let mut records = Vec::new();
+    for card in cards {
+        card.write_bytes(&mut records)?;
+    }
+
+    writer.write_all(&MAGIC)?;
+    writer.write_all(&[VERSION])?;
+    writer.write_all(&(cards.len() as u32).to_le_bytes())?;
+    writer.write_all(&checksum(&records).to_le_bytes())?;
+    writer.write_all(&records)
+}
+
+/// A card in its text form: `level,name,top,right,bottom,left,element`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Card {
+    pub(super) level: u8,
+    pub(super) name: String,
+    /// Flavor text, only held by the structured format.
+    pub(super) description: String,
+    pub(super) stats: Stats,
+    pub(super) element: Element,
+}
+
+impl Card {
+    const FIELD_SEPARATOR: char = ',';
+
+    /// Names are stored with a one byte length.
+    pub const NAME_MAX_LEN: usize = u8::MAX as usize;
+
+    /// Records are sized with a `u16`, which must hold the longest name along the description.
+    pub const DESCRIPTION_MAX_LEN: usize = u16::MAX as usize - 7 - Self::NAME_MAX_LEN;
+}
+
+impl Card {
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Whether the card list format can hold the card without loss.
+    pub fn fits_list_format(&self) -> bool {
+        !self.name.contains(Self::FIELD_SEPARATOR) && self.description.is_empty()
+    }
+
+    /// Writes the record of the card: its size in bytes as a little endian `u16`, followed by the
+    /// level, the packed stats, the element, the name length and the name. A description follows
+    /// when there is one, as its length as a little endian `u16` and the text.
+    ///
+    /// Fields may be appended in later versions, readers skip what follows the fields they know.
+    pub fn write_bytes<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let len = self.name.len() as u8;
+        let name = self.name.as_bytes();
+        let top_rgt = (self.stats.top << 4) | self.stats.rgt;
+        let btm_lft = (self.stats.btm << 4) | self.stats.lft;
+        let element = self.element as u8;
+
+        let description = self.description.as_bytes();
+        let mut size = 5 + name.len() as u16;
+        if !description.is_empty() {
+            size += 2 + description.len() as u16;
+        }
+
+        writer.write_all(&size.to_le_bytes())?;
+        writer.write_all(&[self.level, top_rgt, btm_lft, element, len])?;
+        writer.write_all(name)?;
+        if !description.is_empty() {
+            writer.write_all(&(description.len() as u16).to_le_bytes())?;
+            writer.write_all(description)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads a record written by `write_bytes`, returning the card and the bytes after it.
+    pub(super) fn read_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), DbError> {
+        let &[s0, s1, ref rest @ ..] = bytes else {
+            return Err(DbError::Truncated);
+        };
+        let (record, rest) = rest
+            .split_at_checked(u16::from_le_bytes([s0, s1]) as usize)
+            .ok_or(DbError::Truncated)?;
+
+        let &[level, ref fields @ ..] = record else {
+            return Err(DbError::Truncated);
+        };
+        let (mut card, fields) = Self::read_legacy_bytes(fields, level)?;
+
+        if let &[d0, d1, ref fields @ ..] = fields {
+            let description = fields
+                .get(..u16::from_le_bytes([d0, d1]) as usize)
+                .ok_or(DbError::Truncated)?;
+            card.description =
+                String::from_utf8(description.to_vec()).map_err(|_| DbError::InvalidDescription)?;
+        }
+
+        Ok((card, rest))
+    }
+
+    /// Reads a record of the versions before 2, which lack the size and the level.
+    pub(super) fn read_legacy_bytes(bytes: &[u8], level: u8) -> Result<(Self, &[u8]), DbError> {
+        let &[top_rgt, btm_lft, element, len, ref rest @ ..] = bytes else {
+            return Err(DbError::Truncated);
+        };
+        let (name, rest) = rest
+            .split_at_checked(len as usize)
+            .ok_or(DbError::Truncated)?;
+
+        let stats: Stats = [top_rgt, btm_lft].into();
+        if !stats.is_valid() {
+            return Err(DbError::InvalidStats);
+        }
+
+        let card = Card {
+            level,
+            name: String::from_utf8(name.to_vec()).map_err(|_| DbError::InvalidName)?,
+            description: String::new(),
+            stats,
+            element: element.into(),
+        };
+
+        Ok((card, rest))
+    }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sep = Self::FIELD_SEPARATOR;
+        let Stats { top, rgt, btm, lft } = self.stats;
+
+        write!(
+            f,
+            "{}{sep}{}{sep}{top}{sep}{rgt}{sep}{btm}{sep}{lft}{sep}{}",
+            self.level, self.name, self.element
+        )
+    }
+}
+
+impl FromStr for Card {
+    type Err = DataError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).map_err(|(error, _)| error)
+    }
+}
+
+impl Card {
+    /// Parses and validates a card, failing with the 1-based column of the invalid field.
+    fn parse(s: &str) -> Result<Self, (DataError, usize)> {
+        let mut offset = 0;
+        let mut fields = s.split(Self::FIELD_SEPARATOR).map(|field| {
+            let column = offset + 1;
+            offset += field.len() + 1;
+            (field, column)
+        });
+        // a missing field is reported past the end of the line
+        let end = s.len() + 1;
+
+        let stat = |field: Option<(&str, usize)>, err: DataError| {
+            let (field, column) = field.ok_or((err, end))?;
+            match field.parse::<u8>() {
+                Ok(value) if Stats::RANGE.contains(&value) => Ok(value),
+                _ => Err((err, column)),
+            }
+        };
+
+        let level = stat(fields.next(), DataError::InvalidLevel)?;
+
+        let (name, column) = fields.next().ok_or((DataError::InvalidName, end))?;
+        if name.is_empty() || name.len() > Self::NAME_MAX_LEN {
+            return Err((DataError::InvalidName, column));
+        }
+
+        let top = stat(fields.next(), DataError::InvalidTopStat)?;
+        let rgt = stat(fields.next(), DataError::InvalidRightStat)?;
+        let btm = stat(fields.next(), DataError::InvalidBottomStat)?;
+        let lft = stat(fields.next(), DataError::InvalidLeftStat)?;
+
+        let (element, column) = fields.next().ok_or((DataError::InvalidElement, end))?;
+        let element = Element::from_str(element).map_err(|e| (e, column))?;
+
+        if let Some((_, column)) = fields.next() {
+            return Err((DataError::TrailingField, column));
+        }
+
+        Ok(Card {
+            level,
+            name: name.to_string(),
+            description: String::new(),
+            stats: Stats { top, rgt, btm, lft },
+            element,
+        })
+    }
+}
+
+/// Kind of card list, which sets the number of cards expected.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CardSet {
+    /// Exactly `CARD_COUNT` cards.
+    Base,
+    /// Up to the room left by the base set, `MAX_CARDS - CARD_COUNT`.
+    Expansion,
+}
+
+/// Parses a card list, one card per line and blank lines skipped, and validates it as a whole:
+/// names are unique and the card count fits `set`.
+///
+/// Every invalid line is reported, in order.
+pub fn parse_cards(s: &str, set: CardSet) -> Result<Vec<Card>, Vec<CardError>> {
+    let mut list = CardList::default();
+
+    for (j, line) in s.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match Card::parse(line) {
+            // the name follows the level
+            Ok(card) => {
+                let column = line.find(Card::FIELD_SEPARATOR).map_or(1, |j| j + 2);
+                list.push(card, j + 1, column);
+            }
+            Err((error, column)) => list.error(j + 1, column, error),
+        }
+    }
+
+    list.finish(s.lines().count(), set)
+}
+
+/// Cards being parsed, with the errors found so far.
+#[derive(Default)]
+struct CardList {
+    cards: Vec<Card>,
+    errors: Vec<CardError>,
+}
+
+impl CardList {
+    /// Adds `card`, defined at `line` with its name at `column`.
+    fn push(&mut self, card: Card, line: usize, column: usize) {
+        if self
+            .cards
+            .iter()
+            .any(|other| same_name(&other.name, &card.name))
+        {
+            self.error(line, column, DataError::DuplicateName);
+        } else {
+            self.cards.push(card);
+        }
+    }
+
+    fn error(&mut self, line: usize, column: usize, error: DataError) {
+        self.errors.push(CardError {
+            line,
+            column,
+            error,
+        });
+    }
+
+    /// Checks the card count, reported past the last of `lines`.
+    fn finish(mut self, lines: usize, set: CardSet) -> Result<Vec<Card>, Vec<CardError>> {
+        let found = self.cards.len();
+        let max = MAX_CARDS - CARD_COUNT;
+
+        if self.errors.is_empty() {
+            match set {
+                CardSet::Base if found != CARD_COUNT => self.error(
+                    lines + 1,
+                    1,
+                    DataError::CardCount {
+                        expected: CARD_COUNT,
+                        found,
+                    },
+                ),
+                CardSet::Expansion if found > max => {
+                    self.error(lines + 1, 1, DataError::TooManyCards { max, found })
+                }
+                _ => {}
+            }
+        }
+
+        if self.errors.is_empty() {
+            Ok(self.cards)
+        } else {
+            Err(self.errors)
+        }
+    }
+}
+
+/// Parses cards in the structured format, a subset of TOML where every card is a `[[card]]` table:
+///
+/// ```toml
+/// [[card]]
+/// name = "Geezard"
+/// level = 1
+/// stats = [1, 4, 1, 5] # top, right, bottom, left
+/// element = "None"
+/// description = "Optional."
+/// ```
+///
+/// Values are basic strings, integers or arrays of integers. The cards are validated like the
+/// card list, and every invalid field is reported, in order.
+pub fn parse_cards_toml(s: &str, set: CardSet) -> Result<Vec<Card>, Vec<CardError>> {
+    let mut list = CardList::default();
+    let mut table: Option<CardTable> = None;
+
+    for (j, line) in s.lines().enumerate() {
+        let line_no = j + 1;
+        let start = line.len() - line.trim_start().len();
+        let rest = line[start..].trim_end();
+
+        if rest.is_empty() || rest.starts_with('#') {
+            continue;
+        }
+
+        if rest.starts_with('[') {
+            if rest.split('#').next().map(str::trim_end) != Some("[[card]]") {
+                list.error(line_no, start + 1, DataError::InvalidSyntax);
+                continue;
+            }
+            if let Some(table) = table.take() {
+                table.finish(&mut list);
+            }
+            table = Some(CardTable::new(line_no));
+            continue;
+        }
+
+        let Some(table) = table.as_mut() else {
+            list.error(line_no, start + 1, DataError::InvalidSyntax);
+            continue;
+        };
+        if let Err((error, column)) = table.parse_field(line, start) {
+            list.error(line_no, column, error);
+        }
+    }
+
+    if let Some(table) = table.take() {
+        table.finish(&mut list);
+    }
+
+    list.finish(s.lines().count(), set)
+}
+
+/// Writes `cards` in the structured format read by `parse_cards_toml`.
+pub fn write_cards_toml<W: Write>(cards: &[Card], writer: &mut W) -> io::Result<()> {
+    for (j, card) in cards.iter().enumerate() {
+        if j > 0 {
+            writeln!(writer)?;
+        }
+
+        let Stats { top, rgt, btm, lft } = card.stats;
+        writeln!(writer, "[[card]]")?;
+        writeln!(writer, "name = {}", TomlStr(&card.name))?;
+        writeln!(writer, "level = {}", card.level)?;
+        writeln!(writer, "stats = [{top}, {rgt}, {btm}, {lft}]")?;
+        writeln!(writer, "element = {}", TomlStr(&card.element.to_string()))?;
+        if !card.description.is_empty() {
+            writeln!(writer, "description = {}", TomlStr(&card.description))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// A `[[card]]` table being parsed. Fields are kept with the column of their value.
+struct CardTable {
+    line: usize,
+    name: Option<(String, usize)>,
+    level: Option<u8>,
+    stats: Option<Stats>,
+    element: Option<Element>,
+    description: Option<String>,
+}
+
+impl CardTable {
+    fn new(line: usize) -> Self {
+        Self {
+            line,
+            name: None,
+            level: None,
+            stats: None,
+            element: None,
+            description: None,
+        }
+    }
+
+    /// Parses the `key = value` pair of `line`, whose key starts at byte `start`.
+    fn parse_field(&mut self, line: &str, start: usize) -> Result<(), (DataError, usize)> {
+        let eq = line
+            .find('=')
+            .ok_or((DataError::InvalidSyntax, start + 1))?;
+        let key = line[start..eq].trim_end();
+
+        let value_start = eq + 1 + (line[eq + 1..].len() - line[eq + 1..].trim_start().len());
+        let column = value_start + 1;
+        let (value, len) = TomlValue::parse(&line[value_start..])
+            .map_err(|offset| (DataError::InvalidSyntax, column + offset))?;
+
+        let after = &line[value_start + len..];
+        let trailing = after.trim_start();
+        if !trailing.is_empty() && !trailing.starts_with('#') {
+            let offset = value_start + len + (after.len() - trailing.len());
+            return Err((DataError::InvalidSyntax, offset + 1));
+        }
+
+        let stat = |value: i64, err: DataError| {
+            u8::try_from(value)
+                .ok()
+                .filter(|value| Stats::RANGE.contains(value))
+                .ok_or((err, column))
+        };
+
+        match (key, value) {
+            ("name", TomlValue::Str(name)) if self.name.is_none() => {
+                if name.is_empty() || name.len() > Card::NAME_MAX_LEN {
+                    return Err((DataError::InvalidName, column));
+                }
+                self.name = Some((name, column));
+            }
+            ("level", TomlValue::Int(level)) if self.level.is_none() => {
+                self.level = Some(stat(level, DataError::InvalidLevel)?);
+            }
+            ("stats", TomlValue::Array(values)) if self.stats.is_none() => {
+                let &[top, rgt, btm, lft] = values.as_slice() else {
+                    return Err((DataError::InvalidSyntax, column));
+                };
+                self.stats = Some(Stats {
+                    top: stat(top, DataError::InvalidTopStat)?,
+                    rgt: stat(rgt, DataError::InvalidRightStat)?,
+                    btm: stat(btm, DataError::InvalidBottomStat)?,
+                    lft: stat(lft, DataError::InvalidLeftStat)?,
+                });
+            }
+            ("element", TomlValue::Str(element)) if self.element.is_none() => {
+                self.element = Some(element.parse().map_err(|e| (e, column))?);
+            }
+            ("description", TomlValue::Str(description)) if self.description.is_none() => {
+                if description.len() > Card::DESCRIPTION_MAX_LEN {
+                    return Err((DataError::InvalidDescription, column));
+                }
+                self.description = Some(description);
+            }
+
+            ("name" | "level" | "stats" | "element" | "description", _) if self.is_set(key) => {
+                return Err((DataError::DuplicateField, start + 1));
+            }
+            ("name", _) => return Err((DataError::InvalidName, column)),
+            ("level", _) => return Err((DataError::InvalidLevel, column)),
+            ("stats", _) => return Err((DataError::InvalidSyntax, column)),
+            ("element", _) => return Err((DataError::InvalidElement, column)),
+            ("description", _) => return Err((DataError::InvalidDescription, column)),
+            _ => return Err((DataError::UnknownField, start + 1)),
+        }
+
+        Ok(())
+    }
+
+    fn is_set(&self, key: &str) -> bool {
+        match key {
+            "name" => self.name.is_some(),
+            "level" => self.level.is_some(),
+            "stats" => self.stats.is_some(),
+            "element" => self.element.is_some(),
+            "description" => self.description.is_some(),
+            _ => false,
+        }
+    }
+
+    /// Adds the card to `list`.
+    fn finish(self, list: &mut CardList) {
+        let line = self.line;
+        match self.into_card() {
+            Ok((card, column)) => list.push(card, line, column),
+            Err(error) => list.errors.push(error),
+        }
+    }
+
+    /// Returns the card with the column of its name, or the first missing field reported at the
+    /// table header.
+    fn into_card(self) -> Result<(Card, usize), CardError> {
+        let missing = |field| CardError {
+            line: self.line,
+            column: 1,
+            error: DataError::MissingField(field),
+        };
+
+        let (name, column) = self.name.ok_or_else(|| missing("name"))?;
+        let card = Card {
+            level: self.level.ok_or_else(|| missing("level"))?,
+            name,
+            description: self.description.unwrap_or_default(),
+            stats: self.stats.ok_or_else(|| missing("stats"))?,
+            element: self.element.ok_or_else(|| missing("element"))?,
+        };
+
+        Ok((card, column))
+    }
+}
+
+enum TomlValue {
+    Int(i64),
+    Str(String),
+    Array(Vec<i64>),
+}
+
+impl TomlValue {
+    /// Parses the value at the start of `s`, returning it with its length in bytes, or the offset
+    /// of the syntax error.
+    fn parse(s: &str) -> Result<(Self, usize), usize> {
+        match s.as_bytes().first() {
+            Some(b'"') => {
+                let (value, len) = Self::parse_str(&s[1..]).map_err(|offset| offset + 1)?;
+                Ok((TomlValue::Str(value), len + 1))
+            }
+            Some(b'[') => {
+                let end = s.find(']').ok_or(s.len())?;
+                let mut values = Vec::new();
+                let mut offset = 1;
+
+                for item in s[1..end].split(',') {
+                    let trimmed = item.trim();
+                    // a trailing comma is allowed
+                    if !trimmed.is_empty() || offset + item.len() < end {
+                        let start = offset + (item.len() - item.trim_start().len());
+                        values.push(trimmed.parse().map_err(|_| start)?);
+                    }
+                    offset += item.len() + 1;
+                }
+
+                Ok((TomlValue::Array(values), end + 1))
+            }
+            Some(_) => {
+                let len = s
+                    .find(|c: char| c.is_whitespace() || c == '#')
+                    .unwrap_or(s.len());
+                let value = s[..len].replace('_', "").parse().map_err(|_| 0usize)?;
+                Ok((TomlValue::Int(value), len))
+            }
+            None => Err(0),
+        }
+    }
+
+    /// Parses the rest of a basic string after its opening quote.
+    fn parse_str(s: &str) -> Result<(String, usize), usize> {
+        let mut value = String::new();
+        let mut chars = s.char_indices();
+
+        while let Some((j, c)) = chars.next() {
+            match c {
+                '"' => return Ok((value, j + 1)),
+                '\\' => {
+                    let escaped = match chars.next() {
+                        Some((_, 'n')) => '\n',
+                        Some((_, 't')) => '\t',
+                        Some((_, 'r')) => '\r',
+                        Some((_, '"')) => '"',
+                        Some((_, '\\')) => '\\',
+                        Some((k, 'u')) => {
+                            let hex = s.get(k + 1..k + 5).ok_or(j)?;
+                            let c = u32::from_str_radix(hex, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .ok_or(j)?;
+                            chars.nth(3);
+                            c
+                        }
+                        _ => return Err(j),
+                    };
+                    value.push(escaped);
+                }
+                c if c.is_control() => return Err(j),
+                c => value.push(c),
+            }
+        }
+
+        // unterminated
+        Err(s.len())
+    }
+}
+
+/// A string written as a TOML basic string.
+struct TomlStr<'a>(&'a str);
+
+impl fmt::Display for TomlStr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"")?;
+        for c in self.0.chars() {
+            match c {
+                '"' => write!(f, "\\\"")?,
+                '\\' => write!(f, "\\\\")?,
+                '\n' => write!(f, "\\n")?,
+                '\t' => write!(f, "\\t")?,
+                '\r' => write!(f, "\\r")?,
+                c if c.is_control() => write!(f, "\\u{:04X}", c as u32)?,
+                c => write!(f, "{c}")?,
+            }
+        }
+        write!(f, "\"")
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub top: u8,
+    pub rgt: u8,
+    pub btm: u8,
+    pub lft: u8,
+}
+
+impl Stats {
+    /// Valid stats, levels share the same range.
+    pub const RANGE: RangeInclusive<u8> = 1..=10;
+
+    pub fn is_valid(&self) -> bool {
+        [self.top, self.rgt, self.btm, self.lft]
+            .iter()
+            .all(|stat| Self::RANGE.contains(stat))
+    }
+}
+
+impl From<[u8; 2]> for Stats {
+    fn from(value: [u8; 2]) -> Self {
+        let [top_rgt, btm_lft] = value;
+
+        let top = top_rgt >> 4;
+        let rgt = top_rgt & 0xF;
+        let btm = btm_lft >> 4;
+        let lft = btm_lft & 0xF;
+
+        Stats { top, rgt, btm, lft }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum Element {
+    None = 0,
+    Earth = 1,
+    Fire = 2,
+    Holy = 3,
+    Ice = 4,
+    Poison = 5,
+    Thunder = 6,
+    Water = 7,
+    Wind = 8,
+}
+
+impl From<u8> for Element {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Element::Earth,
+            2 => Element::Fire,
+            3 => Element::Holy,
+            4 => Element::Ice,
+            5 => Element::Poison,
+            6 => Element::Thunder,
+            7 => Element::Water,
+            8 => Element::Wind,
+            _ => Element::None,
+        }
+    }
+}
+
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl FromStr for Element {
+    type Err = DataError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let element = match s {
+            "None" => Self::None,
+            "Earth" => Self::Earth,
+            "Fire" => Self::Fire,
+            "Holy" => Self::Holy,
+            "Ice" => Self::Ice,
+            "Poison" => Self::Poison,
+            "Thunder" => Self::Thunder,
+            "Water" => Self::Water,
+            "Wind" => Self::Wind,
+            _ => return Err(DataError::InvalidElement),
+        };
+
+        Ok(element)
+    }
+}
+
+/// Whether two card names are the same, ignoring case as lookups by name do.
+pub fn same_name(a: &str, b: &str) -> bool {
+    a.to_lowercase() == b.to_lowercase()
+}
+
+/// FNV-1a hash of `bytes`.
+pub fn checksum(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5, |hash, &b| {
+        (hash ^ b as u32).wrapping_mul(0x0100_0193)
+    })
+}
+
+#[derive(Debug)]
+pub enum DbError {
+    Io(io::Error),
+    Empty,
+    /// Neither a database with a header nor records of version 0.
+    UnknownFormat,
+    /// The file ends in the middle of a record, or of the header.
+    Truncated,
+    UnsupportedVersion(u8),
+    /// The records don't match the checksum of the header.
+    ChecksumMismatch,
+    /// The number of records doesn't match the count of the header.
+    CountMismatch {
+        expected: usize,
+        found: usize,
+    },
+    InvalidName,
+    InvalidDescription,
+    /// A stat is out of `Stats::RANGE`.
+    InvalidStats,
+    /// More records than `CardId` can index.
+    TooManyCards {
+        max: usize,
+        found: usize,
+    },
+}
+
+impl From<io::Error> for DbError {
+    fn from(e: io::Error) -> Self {
+        DbError::Io(e)
+    }
+}
+
+impl From<DbError> for io::Error {
+    fn from(e: DbError) -> Self {
+        match e {
+            DbError::Io(e) => e,
+            e => io::Error::new(io::ErrorKind::InvalidData, e.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for DbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DbError::Io(e) => write!(f, "{e}"),
+            DbError::Empty => write!(f, "empty card database"),
+            DbError::UnknownFormat => write!(f, "not a card database"),
+            DbError::Truncated => write!(f, "truncated card database"),
+            DbError::UnsupportedVersion(version) => {
+                write!(f, "unsupported card database version {version}")
+            }
+            DbError::ChecksumMismatch => write!(f, "card database checksum mismatch"),
+            DbError::CountMismatch { expected, found } => {
+                write!(f, "expected {expected} cards in database, found {found}")
+            }
+            DbError::InvalidName => write!(f, "card name is not valid UTF-8"),
+            DbError::InvalidDescription => write!(f, "card description is not valid UTF-8"),
+            DbError::InvalidStats => write!(f, "card stats out of range"),
+            DbError::TooManyCards { max, found } => {
+                write!(f, "{found} cards exceed the maximum of {max}")
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum DataError {
+    CardCount { expected: usize, found: usize },
+    DuplicateField,
+    DuplicateName,
+    InvalidBottomStat,
+    InvalidDescription,
+    InvalidElement,
+    InvalidLeftStat,
+    InvalidLevel,
+    InvalidName,
+    InvalidRightStat,
+    InvalidSyntax,
+    InvalidTopStat,
+    MissingField(&'static str),
+    TooManyCards { max: usize, found: usize },
+    TrailingField,
+    UnknownField,
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let field = match self {
+            DataError::CardCount { expected, found } => {
+                return write!(f, "expected {expected} cards, found {found}");
+            }
+            DataError::DuplicateField => return write!(f, "duplicate field"),
+            DataError::DuplicateName => return write!(f, "duplicate name"),
+            DataError::MissingField(field) => return write!(f, "missing field '{field}'"),
+            DataError::TooManyCards { max, found } => {
+                return write!(f, "expected at most {max} cards, found {found}");
+            }
+            DataError::TrailingField => return write!(f, "unexpected trailing field"),
+            DataError::UnknownField => return write!(f, "unknown field"),
+            DataError::InvalidBottomStat => "bottom stat",
+            DataError::InvalidDescription => "description",
+            DataError::InvalidElement => "element",
+            DataError::InvalidLeftStat => "left stat",
+            DataError::InvalidLevel => "level",
+            DataError::InvalidName => "name",
+            DataError::InvalidRightStat => "right stat",
+            DataError::InvalidSyntax => "syntax",
+            DataError::InvalidTopStat => "top stat",
+        };
+
+        write!(f, "invalid {field}")
+    }
+}
+
+/// Invalid card in a card list, located by 1-based line and column.
+#[derive(Clone, Copy, Debug)]
+pub struct CardError {
+    pub line: usize,
+    pub column: usize,
+    pub error: DataError,
+}
+
+impl fmt::Display for CardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.error)
+    }
+}
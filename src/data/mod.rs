@@ -0,0 +1,768 @@
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufReader, Read, Write},
+    ops::{Range, RangeInclusive},
+    path::Path,
+    str::FromStr,
+};
+
+use crate::core::{battle::Pool, data::CardId};
+
+pub use cards::{
+    Card, CardError, CardSet, DataError, DbError, Element, Stats, parse_cards, parse_cards_toml,
+    write_cards_toml,
+};
+use cards::{checksum, same_name};
+
+/// The card formats alone, which the build script compiles the embedded database with.
+mod cards;
+
+// `cards` can't name `CardId`, so it holds its own copy of the bound
+const _: () = assert!(cards::MAX_CARDS == CardId::MAX as usize);
+
+/// The cards of the base set followed by those of the expansions, each set under its own
+/// namespace.
+#[derive(Debug)]
+pub struct CardDb {
+    pub descriptions: Vec<String>,
+    pub elements: Vec<Element>,
+    pub levels: Vec<u8>,
+    pub names: Vec<String>,
+    pub stats: Vec<Stats>,
+    pub namespaces: Vec<Namespace>,
+}
+
+/// A set of cards, e.g. an expansion, and the indices of its cards in the database.
+#[derive(Clone, Debug)]
+pub struct Namespace {
+    pub name: String,
+    pub cards: Range<usize>,
+}
+
+impl CardDb {
+    /// Cards of the base set.
+    pub const CARD_COUNT: usize = cards::CARD_COUNT;
+
+    pub const BASE_NAMESPACE: &str = "base";
+
+    /// Cards per level in databases older than version 2, which don't store levels but keep cards
+    /// sorted by level.
+    pub const LEVEL_SIZE: usize = 11;
+
+    pub const MAGIC: [u8; 4] = cards::MAGIC;
+    pub const VERSION: u8 = cards::VERSION;
+
+    /// Magic, version, card count and checksum.
+    const HEADER_SIZE: usize = 13;
+
+    /// Reads a database: the `MAGIC` bytes, the format version, the card count and the checksum of
+    /// the records, little endian, followed by the records.
+    ///
+    /// Version 0 files are the bare records, they're told apart by the missing magic.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DbError> {
+        if bytes.is_empty() {
+            return Err(DbError::Empty);
+        }
+
+        let Some(header) = bytes.strip_prefix(&Self::MAGIC) else {
+            return Self::from_legacy_bytes(bytes);
+        };
+
+        let [version, c0, c1, c2, c3, s0, s1, s2, s3, ..] = *header else {
+            return Err(DbError::Truncated);
+        };
+        if version > Self::VERSION {
+            return Err(DbError::UnsupportedVersion(version));
+        }
+
+        // a truncated file is reported as such rather than by its checksum
+        let records = &bytes[Self::HEADER_SIZE..];
+        let card_db = Self::from_records(records, version)?;
+
+        let count = u32::from_le_bytes([c0, c1, c2, c3]) as usize;
+        if card_db.len() != count {
+            return Err(DbError::CountMismatch {
+                expected: count,
+                found: card_db.len(),
+            });
+        }
+        if checksum(records) != u32::from_le_bytes([s0, s1, s2, s3]) {
+            return Err(DbError::ChecksumMismatch);
+        }
+
+        Ok(card_db)
+    }
+
+    /// Reads a version 0 file, which has no header to tell it from any other file. Unless it reads
+    /// to the last byte as a base set of named cards with valid stats, it's taken for a foreign
+    /// file.
+    fn from_legacy_bytes(bytes: &[u8]) -> Result<Self, DbError> {
+        Self::from_records(bytes, 0)
+            .ok()
+            .filter(|card_db| {
+                card_db.len() <= Self::CARD_COUNT && card_db.names.iter().all(|n| !n.is_empty())
+            })
+            .ok_or(DbError::UnknownFormat)
+    }
+
+    fn from_records(mut records: &[u8], version: u8) -> Result<Self, DbError> {
+        let mut cards = Vec::with_capacity(Self::CARD_COUNT);
+
+        while !records.is_empty() {
+            let (card, rest) = if version >= 2 {
+                Card::read_bytes(records)?
+            } else {
+                let level = (cards.len() / Self::LEVEL_SIZE + 1) as u8;
+                Card::read_legacy_bytes(records, level)?
+            };
+
+            cards.push(card);
+            records = rest;
+        }
+
+        if cards.len() > CardId::MAX as usize {
+            return Err(DbError::TooManyCards {
+                max: CardId::MAX as usize,
+                found: cards.len(),
+            });
+        }
+
+        Ok(cards.into_iter().collect())
+    }
+
+    /// Reads the database compiled from `config/cards` into the binary.
+    pub fn embedded() -> Result<Self, DbError> {
+        Self::from_bytes(crate::embed::CARDS_DB)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, DbError> {
+        let mut bytes = Vec::new();
+        BufReader::new(File::open(path)?).read_to_end(&mut bytes)?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Writes `cards` as a database in the current format version, read by `from_bytes`.
+    pub fn write<W: Write>(cards: &[Card], writer: &mut W) -> io::Result<()> {
+        cards::write_db(cards, writer)
+    }
+
+    pub fn len(&self) -> usize {
+        self.stats.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stats.is_empty()
+    }
+
+    pub fn card(&self, j: usize) -> Option<Card> {
+        Some(Card {
+            level: *self.levels.get(j)?,
+            name: self.names.get(j)?.clone(),
+            description: self.descriptions.get(j)?.clone(),
+            stats: *self.stats.get(j)?,
+            element: *self.elements.get(j)?,
+        })
+    }
+
+    pub fn cards(&self) -> impl Iterator<Item = Card> + '_ {
+        (0..self.len()).filter_map(|j| self.card(j))
+    }
+
+    /// Appends the cards of `expansion` under `namespace`.
+    ///
+    /// Card names must stay unique across namespaces, ignoring case, so that cards can be told
+    /// apart by name.
+    pub fn merge(&mut self, namespace: &str, expansion: CardDb) -> Result<(), MergeError> {
+        if namespace.is_empty() || namespace.contains(Namespace::SEPARATOR) {
+            return Err(MergeError::InvalidNamespace(namespace.to_string()));
+        }
+        if self.namespaces.iter().any(|ns| ns.name == namespace) {
+            return Err(MergeError::DuplicateNamespace(namespace.to_string()));
+        }
+
+        let found = self.len() + expansion.len();
+        if found > CardId::MAX as usize {
+            return Err(MergeError::TooManyCards {
+                max: CardId::MAX as usize,
+                found,
+            });
+        }
+
+        for name in &expansion.names {
+            if let Some(j) = self.find(name).map(|card| card.index()) {
+                return Err(MergeError::NameConflict {
+                    name: name.clone(),
+                    namespace: self
+                        .namespace(j)
+                        .map_or_else(String::new, |ns| ns.name.clone()),
+                });
+            }
+        }
+
+        self.namespaces.push(Namespace {
+            name: namespace.to_string(),
+            cards: self.len()..found,
+        });
+        self.descriptions.extend(expansion.descriptions);
+        self.elements.extend(expansion.elements);
+        self.levels.extend(expansion.levels);
+        self.names.extend(expansion.names);
+        self.stats.extend(expansion.stats);
+
+        Ok(())
+    }
+
+    /// Returns the namespace of the card at index `j`.
+    pub fn namespace(&self, j: usize) -> Option<&Namespace> {
+        self.namespaces.iter().find(|ns| ns.cards.contains(&j))
+    }
+
+    /// Returns the card named by `id`: `namespace:name` or `namespace:index`, where the index is
+    /// local to the namespace. Without namespace, an index is global and a name is looked up in
+    /// every namespace.
+    pub fn resolve(&self, id: &str) -> Option<CardId> {
+        let (cards, key) = match id.split_once(Namespace::SEPARATOR) {
+            Some((namespace, key)) => {
+                let ns = self.namespaces.iter().find(|ns| ns.name == namespace)?;
+                (ns.cards.clone(), key)
+            }
+            None => (0..self.len(), id),
+        };
+
+        let j = match key.parse::<usize>() {
+            Ok(index) => cards
+                .start
+                .checked_add(index)
+                .filter(|j| cards.contains(j))?,
+            Err(_) => cards.clone().find(|&j| same_name(&self.names[j], key))?,
+        };
+
+        u8::try_from(j).ok().and_then(CardId::new)
+    }
+
+    /// Returns the id of `card` as `namespace:name`.
+    pub fn qualified_name(&self, card: CardId) -> String {
+        let j = card.index();
+        match self.namespace(j) {
+            Some(ns) => format!("{}{}{}", ns.name, Namespace::SEPARATOR, self.names[j]),
+            None => self.names[j].clone(),
+        }
+    }
+
+    /// Returns the card named `name`, ignoring case.
+    pub fn find(&self, name: &str) -> Option<CardId> {
+        let j = self.names.iter().position(|other| same_name(other, name))?;
+        u8::try_from(j).ok().and_then(CardId::new)
+    }
+
+    /// Returns the cards matching `query`, in database order.
+    pub fn query<'a>(&'a self, query: &'a CardQuery) -> impl Iterator<Item = CardId> + 'a {
+        (0..self.len())
+            .filter(|&j| query.matches(self, j))
+            .filter_map(|j| u8::try_from(j).ok().and_then(CardId::new))
+    }
+
+    /// Returns a hash of the stats and element of the base set cards, in database order, which is
+    /// all the rules see of them. Data computed from them, e.g. an opening book, is stale once it
+    /// changes, but not when expansions are merged as they don't move the base set.
+    pub fn fingerprint(&self) -> u32 {
+        let cards = self
+            .namespaces
+            .iter()
+            .find(|ns| ns.name == Self::BASE_NAMESPACE)
+            .map_or(0..0, |ns| ns.cards.clone());
+
+        let mut bytes = Vec::with_capacity(cards.len() * 5);
+        for j in cards {
+            let Stats { top, rgt, btm, lft } = self.stats[j];
+            bytes.extend_from_slice(&[top, rgt, btm, lft, self.elements[j] as u8]);
+        }
+
+        checksum(&bytes)
+    }
+
+    /// Returns the pool of every card matching `query`.
+    pub fn pool(&self, query: &CardQuery) -> Pool<'static> {
+        self.query(query).collect()
+    }
+}
+
+impl FromIterator<Card> for CardDb {
+    fn from_iter<T: IntoIterator<Item = Card>>(iter: T) -> Self {
+        let mut card_db = CardDb {
+            descriptions: Vec::new(),
+            elements: Vec::new(),
+            levels: Vec::new(),
+            names: Vec::new(),
+            stats: Vec::new(),
+            namespaces: Vec::new(),
+        };
+
+        for card in iter {
+            card_db.descriptions.push(card.description);
+            card_db.elements.push(card.element);
+            card_db.levels.push(card.level);
+            card_db.names.push(card.name);
+            card_db.stats.push(card.stats);
+        }
+
+        card_db.namespaces.push(Namespace {
+            name: Self::BASE_NAMESPACE.to_string(),
+            cards: 0..card_db.len(),
+        });
+
+        card_db
+    }
+}
+
+impl Namespace {
+    pub const SEPARATOR: char = ':';
+}
+
+#[derive(Clone, Debug)]
+pub enum MergeError {
+    DuplicateNamespace(String),
+    /// Namespaces are non-empty and can't contain the `Namespace::SEPARATOR`.
+    InvalidNamespace(String),
+    NameConflict {
+        name: String,
+        namespace: String,
+    },
+    TooManyCards {
+        max: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeError::DuplicateNamespace(ns) => write!(f, "namespace '{ns}' already loaded"),
+            MergeError::InvalidNamespace(ns) => write!(f, "invalid namespace '{ns}'"),
+            MergeError::NameConflict { name, namespace } => {
+                write!(
+                    f,
+                    "card '{name}' already defined in namespace '{namespace}'"
+                )
+            }
+            MergeError::TooManyCards { max, found } => {
+                write!(f, "{found} cards exceed the maximum of {max}")
+            }
+        }
+    }
+}
+
+/// Criteria selecting cards of a `CardDb`, all of which must match.
+///
+/// The text form lists the criteria separated by commas, e.g. `level=1..3,element=Fire,top>=8`:
+/// - `name=<text>`, the name contains the text, ignoring case
+/// - `level=<n>` or `level=<min>..<max>`
+/// - `element=<element>`
+/// - `<side><op><n>`, with `top`, `right`, `bottom`, `left`, `any` or `sum` as side and `<`, `<=`,
+///   `=`, `>=` or `>` as operator
+#[derive(Clone, Debug, Default)]
+pub struct CardQuery {
+    name: Option<String>,
+    levels: Option<RangeInclusive<u8>>,
+    element: Option<Element>,
+    stats: Vec<StatFilter>,
+}
+
+impl CardQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_lowercase());
+        self
+    }
+
+    pub fn with_levels(mut self, levels: RangeInclusive<u8>) -> Self {
+        self.levels = Some(levels);
+        self
+    }
+
+    pub fn with_element(mut self, element: Element) -> Self {
+        self.element = Some(element);
+        self
+    }
+
+    pub fn with_stat(mut self, filter: StatFilter) -> Self {
+        self.stats.push(filter);
+        self
+    }
+
+    /// Whether the card at index `j` of `card_db` matches.
+    pub fn matches(&self, card_db: &CardDb, j: usize) -> bool {
+        self.name
+            .as_ref()
+            .is_none_or(|name| card_db.names[j].to_lowercase().contains(name))
+            && self
+                .levels
+                .as_ref()
+                .is_none_or(|levels| levels.contains(&card_db.levels[j]))
+            && self
+                .element
+                .is_none_or(|element| card_db.elements[j] == element)
+            && self
+                .stats
+                .iter()
+                .all(|filter| filter.matches(&card_db.stats[j]))
+    }
+}
+
+impl FromStr for CardQuery {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut query = CardQuery::new();
+
+        for criterion in s.split(',').map(str::trim).filter(|c| !c.is_empty()) {
+            let err = || QueryError::InvalidCriterion(criterion.to_string());
+
+            query = match criterion.split_once('=') {
+                Some(("name", name)) => query.with_name(name),
+                Some(("level", levels)) => {
+                    let levels = match levels.split_once("..") {
+                        Some((min, max)) => {
+                            min.parse().map_err(|_| err())?..=max.parse().map_err(|_| err())?
+                        }
+                        None => {
+                            let level = levels.parse().map_err(|_| err())?;
+                            level..=level
+                        }
+                    };
+                    query.with_levels(levels)
+                }
+                Some(("element", element)) => {
+                    query.with_element(element.parse().map_err(|_| err())?)
+                }
+                _ => query.with_stat(criterion.parse()?),
+            };
+        }
+
+        Ok(query)
+    }
+}
+
+/// Threshold on a stat of the card, e.g. `top>=8`.
+#[derive(Clone, Copy, Debug)]
+pub struct StatFilter {
+    pub side: StatSide,
+    pub cmp: Cmp,
+    pub value: u8,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StatSide {
+    Top,
+    Right,
+    Bottom,
+    Left,
+    /// Any of the four sides.
+    Any,
+    /// The four sides added up.
+    Sum,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Cmp {
+    Lt,
+    Le,
+    Eq,
+    Ge,
+    Gt,
+}
+
+impl StatFilter {
+    pub fn matches(&self, stats: &Stats) -> bool {
+        let sides = [stats.top, stats.rgt, stats.btm, stats.lft];
+        let check = |value: u16| match self.cmp {
+            Cmp::Lt => value < self.value as u16,
+            Cmp::Le => value <= self.value as u16,
+            Cmp::Eq => value == self.value as u16,
+            Cmp::Ge => value >= self.value as u16,
+            Cmp::Gt => value > self.value as u16,
+        };
+
+        match self.side {
+            StatSide::Top => check(stats.top as u16),
+            StatSide::Right => check(stats.rgt as u16),
+            StatSide::Bottom => check(stats.btm as u16),
+            StatSide::Left => check(stats.lft as u16),
+            StatSide::Any => sides.into_iter().any(|side| check(side as u16)),
+            StatSide::Sum => check(sides.into_iter().map(u16::from).sum()),
+        }
+    }
+}
+
+impl FromStr for StatFilter {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || QueryError::InvalidCriterion(s.to_string());
+
+        let at = s.find(['<', '=', '>']).ok_or_else(err)?;
+        let (side, rest) = s.split_at(at);
+
+        // two character operators first
+        let (cmp, value) = [
+            ("<=", Cmp::Le),
+            (">=", Cmp::Ge),
+            ("<", Cmp::Lt),
+            (">", Cmp::Gt),
+            ("=", Cmp::Eq),
+        ]
+        .into_iter()
+        .find_map(|(op, cmp)| Some((cmp, rest.strip_prefix(op)?)))
+        .ok_or_else(err)?;
+
+        let side = match side.trim() {
+            "top" => StatSide::Top,
+            "right" => StatSide::Right,
+            "bottom" => StatSide::Bottom,
+            "left" => StatSide::Left,
+            "any" => StatSide::Any,
+            "sum" => StatSide::Sum,
+            _ => return Err(err()),
+        };
+
+        Ok(StatFilter {
+            side,
+            cmp,
+            value: value.trim().parse().map_err(|_| err())?,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum QueryError {
+    InvalidCriterion(String),
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryError::InvalidCriterion(criterion) => {
+                write!(f, "invalid card criterion '{criterion}'")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(level: u8, name: &str, description: &str) -> Card {
+        Card {
+            level,
+            name: name.to_string(),
+            description: description.to_string(),
+            stats: Stats {
+                top: 1,
+                rgt: 4,
+                btm: 10,
+                lft: 5,
+            },
+            element: Element::Thunder,
+        }
+    }
+
+    fn cards() -> Vec<Card> {
+        vec![
+            card(1, "Geezard", ""),
+            card(2, "Red, the \"Bat\"", "Flies at night.\nBites."),
+            card(3, "Back\\slash\ttab", "Bell \u{7} and é"),
+        ]
+    }
+
+    fn db_bytes(cards: &[Card]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        CardDb::write(cards, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// Returns a database of `records` in the current version, with a matching header.
+    fn db_of_records(records: &[u8], count: u32) -> Vec<u8> {
+        let mut bytes = CardDb::MAGIC.to_vec();
+        bytes.push(CardDb::VERSION);
+        bytes.extend_from_slice(&count.to_le_bytes());
+        bytes.extend_from_slice(&checksum(records).to_le_bytes());
+        bytes.extend_from_slice(records);
+        bytes
+    }
+
+    #[test]
+    fn toml_round_trip_keeps_escapes_commas_and_descriptions() {
+        let cards = cards();
+        let mut toml = Vec::new();
+        write_cards_toml(&cards, &mut toml).unwrap();
+
+        let parsed = parse_cards_toml(&String::from_utf8(toml).unwrap(), CardSet::Expansion);
+        assert_eq!(parsed.unwrap(), cards);
+    }
+
+    #[test]
+    fn toml_unicode_escape() {
+        let toml = "[[card]]\nname = \"Caf\\u00e9\"\nlevel = 1\nstats = [1, 2, 3, 4]\nelement = \"None\"\n";
+        let cards = parse_cards_toml(toml, CardSet::Expansion).unwrap();
+        assert_eq!(cards[0].name(), "Café");
+    }
+
+    #[test]
+    fn toml_reports_every_invalid_field() {
+        let toml = "[[card]]\nname = \"A\"\nlevel = 11\nstats = [1, 2, 3]\nelement = \"None\"\n";
+        let errors = parse_cards_toml(toml, CardSet::Expansion).unwrap_err();
+
+        // the fields left unset are reported at the table header once it ends
+        let found: Vec<_> = errors.iter().map(|e| (e.line, e.column)).collect();
+        assert_eq!(found, [(3, 9), (4, 9), (1, 1)]);
+        assert!(matches!(errors[0].error, DataError::InvalidLevel));
+        assert!(matches!(errors[1].error, DataError::InvalidSyntax));
+        assert!(matches!(errors[2].error, DataError::MissingField("level")));
+    }
+
+    #[test]
+    fn toml_rejects_descriptions_too_long_for_a_record() {
+        let description = "a".repeat(Card::DESCRIPTION_MAX_LEN + 1);
+        let toml = format!(
+            "[[card]]\nname = \"A\"\nlevel = 1\nstats = [1, 2, 3, 4]\nelement = \"None\"\ndescription = \"{description}\"\n"
+        );
+
+        let errors = parse_cards_toml(&toml, CardSet::Expansion).unwrap_err();
+        assert!(matches!(errors[0].error, DataError::InvalidDescription));
+    }
+
+    #[test]
+    fn names_differing_by_case_are_duplicates() {
+        let list = "1,Geezard,1,4,1,5,None\n1,geezard,1,4,1,5,None\n";
+        let errors = parse_cards(list, CardSet::Expansion).unwrap_err();
+        assert!(matches!(
+            errors[..],
+            [CardError {
+                line: 2,
+                column: 3,
+                error: DataError::DuplicateName
+            }]
+        ));
+    }
+
+    #[test]
+    fn list_round_trip() {
+        let card = card(4, "Geezard", "");
+        assert!(card.fits_list_format());
+        assert_eq!(card.to_string().parse::<Card>().unwrap(), card);
+    }
+
+    #[test]
+    fn list_format_cant_hold_commas_or_descriptions() {
+        let [_, with_comma, with_description] = &cards()[..] else {
+            unreachable!()
+        };
+        assert!(!with_comma.fits_list_format());
+        assert!(!with_description.fits_list_format());
+    }
+
+    #[test]
+    fn db_round_trip_keeps_escapes_commas_and_descriptions() {
+        let cards = cards();
+        let card_db = CardDb::from_bytes(&db_bytes(&cards)).unwrap();
+        assert_eq!(card_db.cards().collect::<Vec<_>>(), cards);
+    }
+
+    #[test]
+    fn db_rejects_truncated_records() {
+        let bytes = db_bytes(&cards());
+        for len in [
+            bytes.len() - 1,
+            CardDb::HEADER_SIZE + 1,
+            CardDb::HEADER_SIZE - 1,
+        ] {
+            let result = CardDb::from_bytes(&bytes[..len]);
+            assert!(matches!(result, Err(DbError::Truncated)), "length {len}");
+        }
+    }
+
+    #[test]
+    fn db_rejects_records_sized_past_the_end() {
+        let mut records = Vec::new();
+        card(1, "Geezard", "").write_bytes(&mut records).unwrap();
+        records[0] += 1;
+
+        let result = CardDb::from_bytes(&db_of_records(&records, 1));
+        assert!(matches!(result, Err(DbError::Truncated)));
+    }
+
+    #[test]
+    fn db_skips_fields_of_later_versions() {
+        let card = card(1, "Geezard", "Flavor.");
+        let mut records = Vec::new();
+        card.write_bytes(&mut records).unwrap();
+        records[0] += 3;
+        records.extend_from_slice(&[1, 2, 3]);
+
+        let card_db = CardDb::from_bytes(&db_of_records(&records, 1)).unwrap();
+        assert_eq!(card_db.cards().collect::<Vec<_>>(), [card]);
+    }
+
+    #[test]
+    fn db_rejects_more_cards_than_card_ids() {
+        let cards: Vec<Card> = (0..=CardId::MAX as usize)
+            .map(|j| card(1, &format!("Card {j}"), ""))
+            .collect();
+
+        let result = CardDb::from_bytes(&db_bytes(&cards));
+        assert!(matches!(
+            result,
+            Err(DbError::TooManyCards {
+                max: 255,
+                found: 256
+            })
+        ));
+    }
+
+    #[test]
+    fn db_rejects_checksum_mismatch() {
+        let mut bytes = db_bytes(&cards());
+        *bytes.last_mut().unwrap() ^= 1;
+
+        let result = CardDb::from_bytes(&bytes);
+        assert!(matches!(result, Err(DbError::ChecksumMismatch)));
+    }
+
+    #[test]
+    fn db_rejects_count_mismatch() {
+        let mut bytes = db_bytes(&cards());
+        bytes[5] = 4;
+
+        let result = CardDb::from_bytes(&bytes);
+        assert!(matches!(
+            result,
+            Err(DbError::CountMismatch {
+                expected: 4,
+                found: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn db_rejects_newer_versions() {
+        let mut bytes = db_bytes(&cards());
+        bytes[4] = CardDb::VERSION + 1;
+
+        let result = CardDb::from_bytes(&bytes);
+        assert!(matches!(result, Err(DbError::UnsupportedVersion(v)) if v == CardDb::VERSION + 1));
+    }
+
+    #[test]
+    fn db_rejects_empty_and_foreign_files() {
+        assert!(matches!(CardDb::from_bytes(&[]), Err(DbError::Empty)));
+        assert!(matches!(
+            CardDb::from_bytes(b"not a database"),
+            Err(DbError::UnknownFormat)
+        ));
+    }
+}
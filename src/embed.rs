@@ -0,0 +1,4 @@
+//! Data compiled into the binary by the build script.
+
+/// The card database compiled from `config/cards`.
+pub static CARDS_DB: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/cards.db"));
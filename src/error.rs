@@ -0,0 +1,162 @@
+use std::{error, fmt, io};
+
+use crate::{
+    core::{battle::Entity, card_pools::PoolError, record::RecordError},
+    data::{DbError, MergeError},
+    locale::LocaleError,
+    profile::ProfileError,
+};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+// ========================================== Error ================================================
+
+/// Error of any subsystem, each converting its own errors into it.
+///
+/// `Context` wraps an error with what was being done when it happened, making up a chain of
+/// sources shown by `Error::report`.
+#[derive(Debug)]
+pub enum Error {
+    /// Invalid command line, or names on it that aren't found.
+    Args(String),
+    Db(DbError),
+    Io(io::Error),
+    Locale(LocaleError),
+    Merge(MergeError),
+    /// A sprite, texture or glyph the `AssetManager` doesn't have.
+    MissingAsset(String),
+    Pool(PoolError),
+    Profile(ProfileError),
+    Record(RecordError),
+    /// Error reported by SDL, which are plain strings.
+    Sdl(String),
+    UnknownEntity(Entity),
+    Context {
+        context: String,
+        source: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Returns the error followed by its chain of sources, one per line.
+    pub fn report(&self) -> Report<'_> {
+        Report(self)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Args(message) => write!(f, "{message}"),
+            Error::Db(e) => write!(f, "{e}"),
+            Error::Io(e) => write!(f, "{e}"),
+            Error::Locale(e) => write!(f, "{e}"),
+            Error::Merge(e) => write!(f, "{e}"),
+            Error::MissingAsset(name) => write!(f, "missing asset: {name}"),
+            Error::Pool(e) => write!(f, "{e}"),
+            Error::Profile(e) => write!(f, "{e}"),
+            Error::Record(e) => write!(f, "{e}"),
+            Error::Sdl(message) => write!(f, "SDL: {message}"),
+            Error::UnknownEntity(entity) => write!(f, "unknown entity {entity:?}"),
+            Error::Context { context, .. } => write!(f, "{context}"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Context { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<DbError> for Error {
+    fn from(e: DbError) -> Self {
+        Error::Db(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<LocaleError> for Error {
+    fn from(e: LocaleError) -> Self {
+        Error::Locale(e)
+    }
+}
+
+impl From<MergeError> for Error {
+    fn from(e: MergeError) -> Self {
+        Error::Merge(e)
+    }
+}
+
+impl From<PoolError> for Error {
+    fn from(e: PoolError) -> Self {
+        Error::Pool(e)
+    }
+}
+
+impl From<ProfileError> for Error {
+    fn from(e: ProfileError) -> Self {
+        Error::Profile(e)
+    }
+}
+
+impl From<RecordError> for Error {
+    fn from(e: RecordError) -> Self {
+        Error::Record(e)
+    }
+}
+
+// ========================================= Context ===============================================
+
+/// Adds to an error what was being done when it happened.
+pub trait Context<T> {
+    fn context(self, context: impl Into<String>) -> Result<T>;
+
+    /// Like `context`, building the context only on error.
+    fn with_context<C: Into<String>>(self, context: impl FnOnce() -> C) -> Result<T>;
+}
+
+impl<T, E: Into<Error>> Context<T> for Result<T, E> {
+    fn context(self, context: impl Into<String>) -> Result<T> {
+        self.with_context(|| context)
+    }
+
+    fn with_context<C: Into<String>>(self, context: impl FnOnce() -> C) -> Result<T> {
+        self.map_err(|e| Error::Context {
+            context: context().into(),
+            source: Box::new(e.into()),
+        })
+    }
+}
+
+// ========================================== Report ===============================================
+
+/// Displays an error and its sources, e.g.
+///
+/// ```text
+/// loading card database mod.db
+///   caused by: card database checksum mismatch
+/// ```
+pub struct Report<'a>(&'a Error);
+
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)?;
+
+        let mut source = error::Error::source(self.0);
+        while let Some(e) = source {
+            write!(f, "\n  caused by: {e}")?;
+            source = e.source();
+        }
+
+        Ok(())
+    }
+}
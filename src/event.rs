@@ -1,22 +1,81 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, fmt, str::FromStr};
 
-use crate::core::battle::{BattleResult, Direction, Entity};
+use crate::core::{
+    battle::{BattleResult, Direction, Entity},
+    game::Move,
+};
 
-#[derive(Debug)]
+/// Player input. The text form, e.g. `confirm` or `up`, is used by scripts and network peers.
+#[derive(Clone, Copy, Debug)]
 pub enum Command {
     Cancel,
     Confirm,
+    Hint,
+    Inference,
     MoveCursor(Direction),
     Quit,
 }
 
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Command::Cancel => "cancel",
+            Command::Confirm => "confirm",
+            Command::Hint => "hint",
+            Command::Inference => "inference",
+            Command::MoveCursor(Direction::Up) => "up",
+            Command::MoveCursor(Direction::Right) => "right",
+            Command::MoveCursor(Direction::Down) => "down",
+            Command::MoveCursor(Direction::Left) => "left",
+            Command::Quit => "quit",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Command {
+    type Err = CommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let command = match s.trim() {
+            "cancel" => Command::Cancel,
+            "confirm" => Command::Confirm,
+            "hint" => Command::Hint,
+            "inference" => Command::Inference,
+            "up" => Command::MoveCursor(Direction::Up),
+            "right" => Command::MoveCursor(Direction::Right),
+            "down" => Command::MoveCursor(Direction::Down),
+            "left" => Command::MoveCursor(Direction::Left),
+            "quit" => Command::Quit,
+            name => return Err(CommandError::UnknownCommand(name.to_string())),
+        };
+
+        Ok(command)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum CommandError {
+    UnknownCommand(String),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::UnknownCommand(name) => write!(f, "unknown command: '{name}'"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum GameEvent {
     CardSelected { target: Entity },
     CardDeselected,
-    CardPlaced,
+    CardPlaced(Move),
     CaptureDetected { target: Entity },
     CardFlipped,
+    HintShown,
     MatchEnded(BattleResult),
 }
 
@@ -1,9 +1,16 @@
+pub mod controller;
 pub mod data;
+pub mod embed;
+pub mod error;
 pub mod event;
+pub mod locale;
+pub mod net;
+pub mod profile;
 pub mod query;
 pub mod render;
 pub mod rules;
 pub mod sdl;
+pub mod settings;
 pub mod systems;
 pub mod ui;
 
@@ -0,0 +1,226 @@
+use std::{
+    collections::HashMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    core::data::CardId,
+    data::CardDb,
+    error::{Context, Result},
+};
+
+/// Locale the others fall back to. Its card names are those of the card database.
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Keys of the UI strings shown to the player.
+pub const UI_KEYS: &[&str] = &[
+    "title",
+    "peer-waiting",
+    "record-saved",
+    "result-p1-win",
+    "result-p2-win",
+    "result-draw",
+    "match-stats",
+];
+
+// ======================================== LocalePack =============================================
+
+/// Translations of one language, read from the file named after its code, e.g. `fr`.
+///
+/// Entries are stored one per line as `key = text`, where the key is `card.<id>` for the name of a
+/// card, the id being resolved by `CardDb::resolve`, or `ui.<key>` for one of `UI_KEYS`. Blank
+/// lines and lines starting with `#` are skipped.
+#[derive(Clone, Debug, Default)]
+pub struct LocalePack {
+    pub code: String,
+    /// Card names by card id, in the order of the file.
+    pub cards: Vec<(String, String)>,
+    pub strings: HashMap<String, String>,
+}
+
+impl LocalePack {
+    const CARD_PREFIX: &str = "card.";
+    const UI_PREFIX: &str = "ui.";
+
+    /// Loads the pack of `code` from `dir`.
+    pub fn load(dir: impl AsRef<Path>, code: &str) -> io::Result<Self> {
+        let content = fs::read_to_string(Self::path(dir, code))?;
+        Self::parse(code, &content)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+    }
+
+    pub fn path(dir: impl AsRef<Path>, code: &str) -> PathBuf {
+        dir.as_ref().join(code)
+    }
+
+    pub fn parse(code: &str, s: &str) -> Result<Self, LocaleError> {
+        let mut pack = LocalePack {
+            code: code.to_string(),
+            ..Default::default()
+        };
+
+        for (j, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let line_nr = j + 1;
+            let (key, text) = line
+                .split_once('=')
+                .ok_or(LocaleError::InvalidEntry { line: line_nr })?;
+            let (key, text) = (key.trim(), text.trim().to_string());
+            let duplicate = || LocaleError::DuplicateKey {
+                line: line_nr,
+                key: key.to_string(),
+            };
+
+            if let Some(id) = key.strip_prefix(Self::CARD_PREFIX) {
+                if pack.cards.iter().any(|(other, _)| other == id) {
+                    return Err(duplicate());
+                }
+                pack.cards.push((id.to_string(), text));
+            } else if let Some(ui_key) = key.strip_prefix(Self::UI_PREFIX) {
+                if pack.strings.insert(ui_key.to_string(), text).is_some() {
+                    return Err(duplicate());
+                }
+            } else {
+                return Err(LocaleError::UnknownKey {
+                    line: line_nr,
+                    key: key.to_string(),
+                });
+            }
+        }
+
+        Ok(pack)
+    }
+
+    /// Returns the translated name of every card of `card_db`, if any, failing on cards unknown to
+    /// it.
+    pub fn card_names(&self, card_db: &CardDb) -> Result<Vec<Option<String>>, LocaleError> {
+        let mut names = vec![None; card_db.len()];
+
+        for (id, name) in &self.cards {
+            let card = card_db
+                .resolve(id)
+                .ok_or_else(|| LocaleError::UnknownCard(id.clone()))?;
+            names[card.index()] = Some(name.clone());
+        }
+
+        Ok(names)
+    }
+}
+
+// ========================================== Locale ===============================================
+
+/// Text shown to the player, in the language of a pack and falling back to `DEFAULT_LOCALE`.
+#[derive(Clone, Debug)]
+pub struct Locale {
+    pub code: String,
+    card_names: Vec<String>,
+    strings: HashMap<String, String>,
+}
+
+impl Locale {
+    /// Loads the pack of `code` from `dir`, along with the default one it falls back to, entirely
+    /// when there is no pack of `code`.
+    pub fn load(dir: impl AsRef<Path>, code: &str, card_db: &CardDb) -> Result<Self> {
+        let dir = dir.as_ref();
+        let context = |code: &str| format!("loading locale '{code}'");
+
+        let fallback =
+            LocalePack::load(dir, DEFAULT_LOCALE).with_context(|| context(DEFAULT_LOCALE))?;
+        let pack = match code {
+            DEFAULT_LOCALE => LocalePack::default(),
+            _ => match LocalePack::load(dir, code) {
+                Ok(pack) => pack,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    eprintln!("ERR: no locale '{code}', falling back to '{DEFAULT_LOCALE}'");
+                    LocalePack::default()
+                }
+                Err(e) => return Err(e).with_context(|| context(code)),
+            },
+        };
+
+        Self::new(pack, fallback, card_db).with_context(|| context(code))
+    }
+
+    /// Combines `pack` with `fallback`, which provides what `pack` doesn't translate.
+    pub fn new(
+        pack: LocalePack,
+        fallback: LocalePack,
+        card_db: &CardDb,
+    ) -> Result<Self, LocaleError> {
+        let translated = pack.card_names(card_db)?;
+        let fallback_names = fallback.card_names(card_db)?;
+
+        let card_names = translated
+            .into_iter()
+            .zip(fallback_names)
+            .zip(&card_db.names)
+            .map(|((name, fallback), default)| name.or(fallback).unwrap_or_else(|| default.clone()))
+            .collect();
+
+        let mut strings = fallback.strings;
+        strings.extend(pack.strings);
+
+        let code = match pack.code.as_str() {
+            "" => fallback.code,
+            _ => pack.code,
+        };
+
+        Ok(Locale {
+            code,
+            card_names,
+            strings,
+        })
+    }
+
+    pub fn card_name(&self, card: CardId) -> &str {
+        &self.card_names[card.index()]
+    }
+
+    /// Returns the UI string of `key`, or the key itself when no pack has it.
+    pub fn text<'a>(&'a self, key: &'a str) -> &'a str {
+        self.strings.get(key).map_or(key, String::as_str)
+    }
+
+    /// Returns the UI string of `key` with each `{name}` placeholder replaced by its argument.
+    pub fn format(&self, key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        args.iter()
+            .fold(self.text(key).to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{name}}}"), &value.to_string())
+            })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum LocaleError {
+    DuplicateKey {
+        line: usize,
+        key: String,
+    },
+    /// A line that isn't `key = text`.
+    InvalidEntry {
+        line: usize,
+    },
+    UnknownCard(String),
+    UnknownKey {
+        line: usize,
+        key: String,
+    },
+}
+
+impl fmt::Display for LocaleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LocaleError::DuplicateKey { line, key } => {
+                write!(f, "line {line}: duplicate key '{key}'")
+            }
+            LocaleError::InvalidEntry { line } => write!(f, "line {line}: expected `key = text`"),
+            LocaleError::UnknownCard(id) => write!(f, "unknown card '{id}'"),
+            LocaleError::UnknownKey { line, key } => write!(f, "line {line}: unknown key '{key}'"),
+        }
+    }
+}
@@ -1,32 +1,275 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
 use sdl2::rect::Rect;
 use triple_triad::{
+    controller::{Controller, ControllerSpec, Script, Seats},
     core::{
-        battle::{Battle, BattleSetup, Player, Pool},
-        card_pools::POOL_BEGINNER,
-        data::CardId,
-        player::Inventory,
+        ai::Agent,
+        analysis::{Analysis, Review},
+        battle::{self, Battle, BattleResult, BattleSetup, HAND_SIZE, Player, Pool},
+        book::OpeningBook,
+        card_pools::{CardPool, PoolError},
+        inference::HandInference,
+        npc::{NPCS, Npc},
+        personality::Personality,
+        record::MatchRecord,
+        rules::RuleSet,
+        stats::MatchStats,
     },
     data::CardDb,
-    event::{self, Command},
+    error::{Context, Error, Result},
+    event::{self, Command, GameEvent},
+    locale::{Locale, LocalePack},
+    net::Peer,
+    profile::Profile,
+    query::get_grid,
     render::RenderCtx,
     sdl::{AssetManager, BakeCardCfg, SdlSystems, Sprite},
     sys::rand::Rng,
     systems::{
-        director_system, flip_system, input_system, placement_system, render_system, rule_system,
-        selection_system, win_system,
+        Hint, Overlays, controller_system, director_system, flip_system, hint_system,
+        inference_system, input_system, placement_system, record_system, render_review_system,
+        render_system, review_system, rule_system, selection_system, stats_system, win_system,
     },
     ui::UI,
 };
 
-fn main() -> Result<(), String> {
-    let card_db = CardDb::load("config/cards.db").map_err(|e| e.to_string())?;
+const RECORDS_DIR: &str = "records";
+
+const PERSONALITIES_PATH: &str = "config/personalities";
+
+const POOLS_PATH: &str = "config/pools";
+
+const OPENING_BOOK_PATH: &str = "config/openings.book";
+
+const PROFILE_PATH: &str = "saves/profile";
+
+/// Locale packs, one file per locale named after its code.
+const LOCALES_DIR: &str = "config/locales";
+
+const USAGE: &str = "usage: triple-triad [--opponent <name>] [--personality <name>] \
+                     [--rules <rule,...>] [--think-time <ms>] [--p1 <controller>] \
+                     [--p2 <controller>] [--hotseat] [--db <cards.db>] \
+                     [--expansion <path.db>]... [--locale <code>] [--name <name>]
+
+controllers: human, ai, script:<path>, listen:<addr>, connect:<addr>";
+
+struct Args {
+    opponent: &'static Npc,
+    personality: Option<String>,
+    rules: Option<RuleSet>,
+    think_time: Duration,
+    p1: ControllerSpec,
+    p2: ControllerSpec,
+    /// External card database replacing the embedded one, e.g. for mods.
+    db_path: Option<String>,
+    /// Card databases merged after the base set, each under the namespace of its file stem.
+    expansions: Vec<PathBuf>,
+    /// Locale saved to the profile settings.
+    locale: Option<String>,
+    /// Display name saved to the profile.
+    name: Option<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        opponent: &NPCS[0],
+        personality: None,
+        rules: None,
+        think_time: Agent::DEFAULT_BUDGET,
+        p1: ControllerSpec::Human,
+        p2: ControllerSpec::Ai,
+        db_path: None,
+        expansions: Vec::new(),
+        locale: None,
+        name: None,
+    };
+
+    let mut iter = env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--opponent" => {
+                let name = iter.next().ok_or(USAGE)?;
+                args.opponent = Npc::find(&name).ok_or_else(|| {
+                    let names: Vec<&str> = NPCS.iter().map(|npc| npc.name).collect();
+                    format!(
+                        "unknown opponent '{name}', expected one of: {}",
+                        names.join(", ")
+                    )
+                })?;
+            }
+            "--personality" => args.personality = Some(iter.next().ok_or(USAGE)?),
+            "--rules" => {
+                let rules = iter.next().ok_or(USAGE)?;
+                args.rules = Some(rules.parse().map_err(|e| format!("{e}"))?);
+            }
+            "--think-time" => {
+                let ms = iter.next().ok_or(USAGE)?;
+                let ms = ms
+                    .parse()
+                    .map_err(|_| format!("invalid think time '{ms}'"))?;
+                args.think_time = Duration::from_millis(ms);
+            }
+            "--p1" => args.p1 = iter.next().ok_or(USAGE)?.parse()?,
+            "--p2" => args.p2 = iter.next().ok_or(USAGE)?.parse()?,
+            "--hotseat" => args.p2 = ControllerSpec::Human,
+            "--db" => args.db_path = Some(iter.next().ok_or(USAGE)?),
+            "--expansion" => args.expansions.push(iter.next().ok_or(USAGE)?.into()),
+            "--locale" => args.locale = Some(iter.next().ok_or(USAGE)?),
+            "--name" => {
+                let name = iter.next().ok_or(USAGE)?;
+                if !Profile::is_valid_name(&name) {
+                    return Err(format!("invalid name '{}'", name.escape_debug()));
+                }
+                args.name = Some(name);
+            }
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    Ok(args)
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("ERR: {}", e.report());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<()> {
+    let args = parse_args().map_err(Error::Args)?;
+    let mut rules = args.rules.unwrap_or(args.opponent.rules);
+
+    let mut card_db = match &args.db_path {
+        Some(path) => {
+            CardDb::load(path).with_context(|| format!("loading card database {path}"))?
+        }
+        None => CardDb::embedded().context("loading the embedded card database")?,
+    };
+    for path in &args.expansions {
+        let name = path.display();
+        let namespace = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| Error::Args(format!("{name}: invalid expansion name")))?;
+
+        let expansion = CardDb::load(path).with_context(|| format!("loading expansion {name}"))?;
+        card_db
+            .merge(namespace, expansion)
+            .with_context(|| format!("merging expansion {name}"))?;
+    }
+    let card_db = Arc::new(card_db);
+
+    // a corrupt profile is kept aside for recovery, a newer one is left alone
+    let mut profile = match Profile::load(PROFILE_PATH, &card_db) {
+        Ok(profile) => profile.unwrap_or_else(|| Profile::new(&card_db)),
+        Err(e) if e.is_corrupt() => {
+            let backup = Profile::backup(PROFILE_PATH)
+                .with_context(|| format!("backing up {PROFILE_PATH}"))?;
+            eprintln!(
+                "ERR: {PROFILE_PATH}: {e}, backed up to {}, starting a new profile",
+                backup.display()
+            );
+            Profile::new(&card_db)
+        }
+        Err(e) => return Err(e).with_context(|| format!("loading {PROFILE_PATH}")),
+    };
+    if let Some(name) = args.name {
+        profile.name = name;
+    }
+    if let Some(locale) = args.locale {
+        // a code names a file of the locale directory, not a path
+        let is_code = locale
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !is_code || !LocalePack::path(LOCALES_DIR, &locale).is_file() {
+            return Err(Error::Args(format!("unknown locale '{locale}'")));
+        }
+        profile.settings.locale = locale;
+    }
+    profile
+        .save(PROFILE_PATH, &card_db)
+        .with_context(|| format!("saving {PROFILE_PATH}"))?;
+
+    let locale = Locale::load(LOCALES_DIR, &profile.settings.locale, &card_db)?;
+
+    let pools = CardPool::load_all(POOLS_PATH).with_context(|| format!("loading {POOLS_PATH}"))?;
+    let opponent_pool = CardPool::resolve_named(&pools, args.opponent.pool, &card_db)
+        .with_context(|| format!("resolving pool '{}'", args.opponent.pool))?;
+
+    // the book is optional, the AI searches every move without it
+    let book = match OpeningBook::load(OPENING_BOOK_PATH, &card_db) {
+        Ok(book) => Some(Arc::new(book)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => {
+            eprintln!("ERR: loading opening book: {e}");
+            None
+        }
+    };
+
+    let controller = |player: Player, spec: &ControllerSpec| -> Result<Controller> {
+        let controller = match spec {
+            ControllerSpec::Human => Controller::Human,
+            // personalities are only needed by an AI seat
+            ControllerSpec::Ai => {
+                let personalities = Personality::load_all(PERSONALITIES_PATH)
+                    .with_context(|| format!("loading {PERSONALITIES_PATH}"))?;
+                let name = args
+                    .personality
+                    .as_deref()
+                    .unwrap_or(args.opponent.personality);
+                let personality = Personality::find(&personalities, name)
+                    .ok_or_else(|| Error::Args(format!("unknown personality '{name}'")))?;
+
+                Controller::Ai(
+                    Agent::new(
+                        player,
+                        args.opponent.strength,
+                        personality.weights,
+                        card_db.clone(),
+                    )
+                    .with_budget(args.think_time)
+                    .with_book(book.clone()),
+                )
+            }
+            ControllerSpec::Script(path) => Controller::Script(
+                Script::load(path).with_context(|| format!("loading script {}", path.display()))?,
+            ),
+            ControllerSpec::Listen(addr) => {
+                eprintln!("{}", locale.format("peer-waiting", &[("addr", addr)]));
+                Controller::Network(
+                    Peer::listen(addr).with_context(|| format!("listening on {addr}"))?,
+                )
+            }
+            ControllerSpec::Connect(addr) => Controller::Network(
+                Peer::connect(addr).with_context(|| format!("connecting to {addr}"))?,
+            ),
+        };
+
+        Ok(controller)
+    };
+    let mut seats = Seats {
+        p1: controller(Player::P1, &args.p1)?,
+        p2: controller(Player::P2, &args.p2)?,
+    };
+
     let ui = UI::default();
 
     let SdlSystems {
         mut canvas,
         mut event_pump,
         texture_creator,
-    } = SdlSystems::init()?;
+    } = SdlSystems::init(locale.text("title"))?;
 
     let event::Bus {
         mut commands,
@@ -35,7 +278,7 @@ fn main() -> Result<(), String> {
     } = event::Bus::default();
 
     let mut asset_manager = AssetManager::default();
-    asset_manager.load_font(&texture_creator, "assets/font.png")?;
+    asset_manager.load_font(&texture_creator, "assets/font.png", "assets/font.txt")?;
     let t_cards = asset_manager.load_texture(&texture_creator, "assets/cards.png")?;
     let t_ui = asset_manager.load_texture(&texture_creator, "assets/ui.png")?;
     #[cfg_attr(any(), rustfmt::skip)]
@@ -74,16 +317,39 @@ fn main() -> Result<(), String> {
 
     println!("{}", rng);
 
-    let mut inventory = Inventory::default();
-    for j in 99..110 {
-        inventory.add(unsafe { CardId::new_unchecked(j) }, 1);
+    let pool: Pool = profile.inventory.iter_distinct().collect();
+    if pool.0.len() <= HAND_SIZE {
+        return Err(PoolError::TooFewCards(pool.0.len()))
+            .with_context(|| format!("drawing a hand from the cards of {}", profile.name));
     }
 
-    let pool: Pool = inventory.iter_distinct().collect();
-
-    let battle_setup = BattleSetup {
+    let mut battle_setup = BattleSetup {
         p1_hand: pool.draw_hand(&mut rng),
-        p2_hand: POOL_BEGINNER.draw_hand(&mut rng),
+        p2_hand: opponent_pool.draw_hand(&mut rng),
+    };
+
+    // the host deals, the peer plays the rules and hands it receives
+    for (player, spec) in [(Player::P1, &args.p1), (Player::P2, &args.p2)] {
+        let Controller::Network(peer) = seats.get_mut(player) else {
+            continue;
+        };
+
+        let exchange = match spec {
+            ControllerSpec::Connect(_) => peer.recv_setup().map(|setup| {
+                rules = setup.rules;
+                battle_setup = setup.setup;
+            }),
+            _ => peer.send_setup(&MatchRecord::new(rules, battle_setup)),
+        };
+        exchange.with_context(|| format!("exchanging setup with {spec}"))?;
+    }
+
+    // a closed hand is hidden when a single seat is played on this screen
+    let concealed = match (seats.is_human(Player::P1), seats.is_human(Player::P2)) {
+        _ if rules.open => None,
+        (true, false) => Some(Player::P2),
+        (false, true) => Some(Player::P1),
+        _ => None,
     };
 
     let Battle {
@@ -92,10 +358,23 @@ fn main() -> Result<(), String> {
         ..
     } = battle_setup.into();
 
+    let mut hint = Hint::new(rules, concealed, card_db.clone());
+    let mut stats = MatchStats::default();
+    let mut record = MatchRecord::new(rules, battle_setup);
+    let mut review: Option<Review> = None;
+    // the review is shown once the analysis running in the background is done
+    let mut analysis = None;
+    // only the opponent's pool is known, so only an NPC hand can be inferred
+    let mut inference = concealed
+        .filter(|&player| player == Player::P2 && matches!(seats.p2, Controller::Ai(_)))
+        .map(|_| HandInference::new(&opponent_pool));
+    let mut show_inference = false;
+
     let mut render_ctx = RenderCtx {
         asset_manager: &mut asset_manager,
         canvas: &mut canvas,
         ui: &ui,
+        locale: &locale,
     };
 
     'running: loop {
@@ -105,15 +384,135 @@ fn main() -> Result<(), String> {
             break 'running;
         }
 
+        if let battle::State::End { result } = state
+            && review.is_none()
+            && analysis.is_none()
+            && commands.iter().any(|cmd| matches!(cmd, Command::Confirm))
+        {
+            // a Sudden Death draw is replayed with the cards each player ends up owning
+            let rematch = match result {
+                BattleResult::Draw if rules.sudden_death => BattleSetup::rematch(&components),
+                _ => None,
+            };
+
+            match rematch {
+                Some(setup) => {
+                    if let (Some(player), Some(inference)) = (concealed, inference.as_mut()) {
+                        let owned = get_grid(&components)
+                            .into_iter()
+                            .flatten()
+                            .filter(|cell| cell.owner == player)
+                            .map(|cell| cell.card);
+                        inference.rematch(owned);
+                    }
+
+                    Battle { state, components } = setup.into();
+                    record = MatchRecord::new(rules, setup);
+                    stats = MatchStats::default();
+                }
+                None => analysis = Some(Analysis::spawn(record.clone(), card_db.clone())),
+            }
+            commands.clear();
+        }
+
+        if let Some(handle) = analysis.take_if(|handle| handle.is_finished()) {
+            match handle.join() {
+                Ok(result) => match result.context("analyzing the match") {
+                    Ok(analysis) => review = Some(Review::new(analysis)),
+                    Err(e) => eprintln!("ERR: {}", e.report()),
+                },
+                Err(_) => eprintln!("ERR: match analysis panicked"),
+            }
+        }
+
+        if review.is_some() {
+            review_system(&commands, &mut review);
+            if let Some(review) = &review {
+                render_review_system(&mut render_ctx, review, &card_db)?;
+            }
+
+            commands.clear();
+            continue;
+        }
+
+        controller_system(
+            &mut commands,
+            &state,
+            &components,
+            &rules,
+            &mut seats,
+            &mut rng,
+        );
+
+        hint_system(
+            &commands,
+            &mut events,
+            &state,
+            &components,
+            inference.as_ref(),
+            &mut hint,
+            &mut rng,
+        );
         selection_system(&commands, &mut events, &mut state, &components);
         placement_system(&commands, &mut events, &mut state, &mut components);
-        rule_system(&mut flips, &state, &components, &card_db);
+        rule_system(&mut flips, &state, &components, &rules, &card_db);
         flip_system(&mut events, &flips, &mut components.owner);
         win_system(&mut events, state, &components);
-        render_system(&mut render_ctx, &state, &components, &card_db)?;
+        stats_system(&events, &state, &mut stats);
+        record_system(&events, &mut record);
+        if let (Some(player), Some(inference)) = (concealed, inference.as_mut()) {
+            inference_system(
+                &commands,
+                &events,
+                &state,
+                &components,
+                player,
+                inference,
+                &mut show_inference,
+            );
+        }
+        render_system(
+            &mut render_ctx,
+            &state,
+            &components,
+            &card_db,
+            Overlays {
+                concealed,
+                hint: hint.shown,
+                thinking: seats.thinking(),
+                inference: inference.as_ref().filter(|_| show_inference),
+                stats: Some(&stats),
+            },
+        )?;
 
         director_system(&events, &mut state, &components.owner, &components.position);
 
+        if let Some(GameEvent::MatchEnded(result)) = events
+            .iter()
+            .find(|e| matches!(e, GameEvent::MatchEnded(_)))
+        {
+            let result = match result {
+                BattleResult::Win(Player::P1) => "result-p1-win",
+                BattleResult::Win(Player::P2) => "result-p2-win",
+                BattleResult::Draw => "result-draw",
+            };
+            println!("{}", locale.text(result));
+
+            match save_record(&record).context("saving match record") {
+                Ok(path) => eprintln!(
+                    "{}",
+                    locale.format("record-saved", &[("path", &path.display())])
+                ),
+                Err(e) => eprintln!("ERR: {}", e.report()),
+            }
+            if let Err(e) = profile
+                .save(PROFILE_PATH, &card_db)
+                .with_context(|| format!("saving {PROFILE_PATH}"))
+            {
+                eprintln!("ERR: {}", e.report());
+            }
+        }
+
         commands.clear();
         events.clear();
         flips.clear();
@@ -121,3 +520,16 @@ fn main() -> Result<(), String> {
 
     Ok(())
 }
+
+/// Saves `record` under `RECORDS_DIR`, named after the current time.
+fn save_record(record: &MatchRecord) -> io::Result<PathBuf> {
+    fs::create_dir_all(RECORDS_DIR)?;
+
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let path = Path::new(RECORDS_DIR).join(format!("{secs}.txt"));
+    record.save(&path)?;
+
+    Ok(path)
+}
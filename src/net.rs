@@ -0,0 +1,144 @@
+use std::{
+    collections::VecDeque,
+    io::{self, BufRead, BufReader, ErrorKind, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+};
+
+use crate::{core::record::MatchRecord, event::Command};
+
+// ============================================ Peer ===============================================
+
+/// Remote player over TCP.
+///
+/// The host sends the match setup as a `MatchRecord` followed by an empty line, then both ends
+/// exchange the commands of their local player, one per line.
+pub struct Peer {
+    stream: TcpStream,
+    buffer: Vec<u8>,
+    /// Commands received but not yet played, released one per frame.
+    received: VecDeque<Command>,
+    /// Commands not yet accepted by the socket.
+    unsent: Vec<u8>,
+    connected: bool,
+}
+
+impl Peer {
+    /// Waits for a peer to connect to `addr`.
+    pub fn listen(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let (stream, _) = TcpListener::bind(addr)?.accept()?;
+        Self::new(stream)
+    }
+
+    pub fn connect(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        Self::new(TcpStream::connect(addr)?)
+    }
+
+    fn new(stream: TcpStream) -> io::Result<Self> {
+        // commands are a few bytes each, sent as soon as they're issued
+        stream.set_nodelay(true)?;
+
+        Ok(Self {
+            stream,
+            buffer: Vec::new(),
+            received: VecDeque::new(),
+            unsent: Vec::new(),
+            connected: true,
+        })
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    /// Sends the setup of the match, to be called by the host before the match starts.
+    pub fn send_setup(&mut self, record: &MatchRecord) -> io::Result<()> {
+        let setup = MatchRecord::new(record.rules, record.setup);
+        // the setup ends with a newline already, the empty line closes it
+        writeln!(self.stream, "{setup}")?;
+        self.stream.flush()?;
+        self.stream.set_nonblocking(true)
+    }
+
+    /// Waits for the setup of the match sent by the host.
+    pub fn recv_setup(&mut self) -> io::Result<MatchRecord> {
+        let mut reader = BufReader::new(&self.stream);
+        let mut setup = String::new();
+
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 {
+                return Err(ErrorKind::UnexpectedEof.into());
+            }
+            if line.trim().is_empty() {
+                break;
+            }
+            setup.push_str(&line);
+        }
+
+        // commands may have been read along with the setup
+        self.buffer.extend_from_slice(reader.buffer());
+
+        self.stream.set_nonblocking(true)?;
+        setup
+            .parse()
+            .map_err(|e| io::Error::new(ErrorKind::InvalidData, format!("{e}")))
+    }
+
+    /// Sends `command`, or queues it until the socket accepts it.
+    pub fn send(&mut self, command: Command) {
+        if !self.connected {
+            return;
+        }
+
+        self.unsent
+            .extend_from_slice(format!("{command}\n").as_bytes());
+        self.flush();
+    }
+
+    /// Writes the queued commands, without blocking.
+    fn flush(&mut self) {
+        while self.connected && !self.unsent.is_empty() {
+            match self.stream.write(&self.unsent) {
+                Ok(0) => self.disconnect(ErrorKind::WriteZero.into()),
+                Ok(n) => {
+                    self.unsent.drain(..n);
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) => self.disconnect(e),
+            }
+        }
+    }
+
+    /// Returns the next command received, without blocking.
+    ///
+    /// Commands arriving together are returned over successive calls, so that each is played in
+    /// its own frame as a local player's would.
+    pub fn recv(&mut self) -> Option<Command> {
+        self.flush();
+
+        let mut chunk = [0u8; 256];
+        while self.connected {
+            match self.stream.read(&mut chunk) {
+                Ok(0) => self.disconnect(ErrorKind::UnexpectedEof.into()),
+                Ok(n) => self.buffer.extend_from_slice(&chunk[..n]),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) => self.disconnect(e),
+            }
+        }
+
+        while let Some(end) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            match String::from_utf8_lossy(&line).parse() {
+                Ok(command) => self.received.push_back(command),
+                Err(e) => eprintln!("ERR: from peer: {e}"),
+            }
+        }
+
+        self.received.pop_front()
+    }
+
+    fn disconnect(&mut self, e: io::Error) {
+        eprintln!("ERR: peer disconnected: {e}");
+        self.connected = false;
+    }
+}
@@ -0,0 +1,238 @@
+use std::{
+    fmt,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    ops::Range,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    core::{data::CardId, player::Inventory},
+    data::CardDb,
+    settings::Settings,
+};
+
+// ========================================= Profile ===============================================
+
+/// The player's save: display name, settings and the cards owned.
+///
+/// The text format starts with a header naming its version, followed by one entry per line:
+///
+/// ```txt
+/// triple-triad-profile 1
+/// name Squall
+/// setting locale fr
+/// card 2 base:Geezard
+/// card 1 mod:Moogle
+/// ```
+///
+/// where cards are named by `CardDb::qualified_name`, so that saves survive cards being added to
+/// the database.
+#[derive(Clone, Debug)]
+pub struct Profile {
+    pub name: String,
+    pub settings: Settings,
+    pub inventory: Inventory,
+    /// Cards of databases not loaded this run, e.g. an expansion, kept as saved.
+    unknown_cards: Vec<(String, u8)>,
+}
+
+impl Profile {
+    const HEADER: &str = "triple-triad-profile";
+    pub const VERSION: u32 = 1;
+
+    pub const DEFAULT_NAME: &str = "Player";
+
+    /// Cards a new profile starts with, by index in the base set.
+    const STARTER_CARDS: Range<u8> = 99..110;
+
+    /// Returns a new profile owning the starter cards.
+    pub fn new(card_db: &CardDb) -> Self {
+        let mut inventory = Inventory::new(card_db.len());
+        for card in Self::STARTER_CARDS.filter_map(CardId::new) {
+            inventory.add(card, 1);
+        }
+
+        Profile {
+            name: Self::DEFAULT_NAME.to_string(),
+            settings: Settings::default(),
+            inventory,
+            unknown_cards: Vec::new(),
+        }
+    }
+
+    /// Loads the profile at `path`, or `None` when there is no such file.
+    pub fn load(path: impl AsRef<Path>, card_db: &CardDb) -> Result<Option<Self>, ProfileError> {
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(ProfileError::Io(e)),
+        };
+
+        let content = String::from_utf8(bytes).map_err(|_| ProfileError::InvalidUtf8)?;
+        Self::parse(&content, card_db).map(Some)
+    }
+
+    /// Writes the profile next to `path` then renames it over `path`, which is left untouched if
+    /// saving fails midway.
+    pub fn save(&self, path: impl AsRef<Path>, card_db: &CardDb) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let tmp_path = path.with_extension("tmp");
+        let file = File::create(&tmp_path)?;
+        let mut writer = BufWriter::new(&file);
+        self.write(&mut writer, card_db)?;
+        writer.flush()?;
+        drop(writer);
+        file.sync_all()?;
+
+        fs::rename(&tmp_path, path)
+    }
+
+    /// Moves the file at `path` aside, named after the current time, and returns its new path.
+    pub fn backup(path: impl AsRef<Path>) -> io::Result<PathBuf> {
+        let path = path.as_ref();
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        let mut backup = path.as_os_str().to_owned();
+        backup.push(format!(".{secs}.bak"));
+        let backup = PathBuf::from(backup);
+        fs::rename(path, &backup)?;
+
+        Ok(backup)
+    }
+
+    /// Whether `name` can be saved: not blank and without control characters, which would break
+    /// the line it's written on.
+    pub fn is_valid_name(name: &str) -> bool {
+        !name.trim().is_empty() && !name.chars().any(char::is_control)
+    }
+
+    pub fn write(&self, w: &mut impl Write, card_db: &CardDb) -> io::Result<()> {
+        writeln!(w, "{} {}", Self::HEADER, Self::VERSION)?;
+        writeln!(w, "name {}", self.name)?;
+
+        for (key, value) in self.settings.entries() {
+            writeln!(w, "setting {key} {value}")?;
+        }
+
+        for (card, count) in self.inventory.iter() {
+            writeln!(w, "card {count} {}", card_db.qualified_name(card))?;
+        }
+        for (id, count) in &self.unknown_cards {
+            writeln!(w, "card {count} {id}")?;
+        }
+
+        Ok(())
+    }
+
+    pub fn parse(s: &str, card_db: &CardDb) -> Result<Self, ProfileError> {
+        let mut lines = s.lines().enumerate();
+
+        let version = lines
+            .next()
+            .and_then(|(_, line)| line.strip_prefix(Self::HEADER))
+            .and_then(|version| version.trim().parse().ok())
+            .ok_or(ProfileError::MissingHeader)?;
+        if version > Self::VERSION {
+            return Err(ProfileError::UnsupportedVersion(version));
+        }
+
+        let mut profile = Profile {
+            name: Self::DEFAULT_NAME.to_string(),
+            settings: Settings::default(),
+            inventory: Inventory::new(card_db.len()),
+            unknown_cards: Vec::new(),
+        };
+
+        for (j, line) in lines {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let err = |message: String| ProfileError::InvalidEntry {
+                line: j + 1,
+                message,
+            };
+
+            let (tag, rest) = line.split_once(' ').unwrap_or((line, ""));
+            match tag {
+                "name" if !Self::is_valid_name(rest) => {
+                    return Err(err(format!("invalid name '{}'", rest.escape_debug())));
+                }
+                "name" => profile.name = rest.trim().to_string(),
+                "setting" => {
+                    let (key, value) = rest.split_once(' ').unwrap_or((rest, ""));
+                    profile
+                        .settings
+                        .set(key, value.trim())
+                        .map_err(|e| err(e.to_string()))?;
+                }
+                "card" => {
+                    let (count, id) = rest
+                        .split_once(' ')
+                        .ok_or_else(|| err("expected `card <count> <id>`".to_string()))?;
+                    let count: u8 = count
+                        .parse()
+                        .map_err(|_| err(format!("invalid card count '{count}'")))?;
+
+                    match card_db.resolve(id) {
+                        Some(card) => profile.inventory.add(card, count),
+                        None => profile.unknown_cards.push((id.to_string(), count)),
+                    }
+                }
+                _ => return Err(err(format!("unknown entry '{line}'"))),
+            }
+        }
+
+        Ok(profile)
+    }
+}
+
+#[derive(Debug)]
+pub enum ProfileError {
+    Io(io::Error),
+    /// The file isn't text.
+    InvalidUtf8,
+    /// The file doesn't start with the profile header.
+    MissingHeader,
+    /// The profile was saved by a newer version of the game.
+    UnsupportedVersion(u32),
+    InvalidEntry {
+        line: usize,
+        message: String,
+    },
+}
+
+impl ProfileError {
+    /// Whether the file is unreadable as a profile, as opposed to unreadable at all or newer than
+    /// this version of the game.
+    pub fn is_corrupt(&self) -> bool {
+        matches!(
+            self,
+            ProfileError::InvalidUtf8
+                | ProfileError::MissingHeader
+                | ProfileError::InvalidEntry { .. }
+        )
+    }
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileError::Io(e) => write!(f, "{e}"),
+            ProfileError::InvalidUtf8 => write!(f, "profile is not valid UTF-8"),
+            ProfileError::MissingHeader => write!(f, "missing profile header"),
+            ProfileError::UnsupportedVersion(version) => {
+                write!(f, "unsupported profile version {version}")
+            }
+            ProfileError::InvalidEntry { line, message } => write!(f, "line {line}: {message}"),
+        }
+    }
+}
@@ -1,5 +1,11 @@
 use crate::{
-    core::battle::{ComponentArray, Components, Entity, Player, Position},
+    core::{
+        battle::{
+            self, BOARD_SIZE, ComponentArray, Components, Entity, HAND_SIZE, Player, Position,
+        },
+        game::Game,
+        rules::{Cell, Grid},
+    },
     data::{CardDb, Stats},
 };
 
@@ -67,3 +73,48 @@ pub fn hand_size(
         .filter(|&e| owners[e] == Some(player) && matches!(positions[e], Some(Position::Hand(_))))
         .count()
 }
+
+/// Returns the cards placed on the board with their current owners.
+pub fn get_grid(components: &Components) -> Grid {
+    let mut grid: Grid = [None; BOARD_SIZE * BOARD_SIZE];
+
+    for entity in Entity::iter() {
+        let (Some(Position::Board(coords)), Some(owner), Some(card)) = (
+            components.position[entity],
+            components.owner[entity],
+            components.card[entity],
+        ) else {
+            continue;
+        };
+
+        grid[coords.index()] = Some(Cell { card, owner });
+    }
+
+    grid
+}
+
+/// Returns a `Game` snapshot of the match, or `None` when no turn is in progress.
+pub fn get_game(state: &battle::State, components: &Components) -> Option<Game> {
+    let &battle::State::Turn { player, .. } = state else {
+        return None;
+    };
+
+    let mut hands = [[None; HAND_SIZE]; 2];
+    for entity in Entity::iter() {
+        let (Some(Position::Hand(j)), Some(owner), Some(card)) = (
+            components.position[entity],
+            components.owner[entity],
+            components.card[entity],
+        ) else {
+            continue;
+        };
+
+        hands[owner as usize][j] = Some(card);
+    }
+
+    Some(Game {
+        grid: get_grid(components),
+        hands,
+        turn: player,
+    })
+}
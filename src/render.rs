@@ -1,11 +1,17 @@
 use crate::{
-    core::battle::{Components, Entity, Player, Position},
+    core::{
+        battle::{self, Components, Entity, Player, Position, TurnPhase},
+        game::Move,
+    },
     data::CardDb,
-    query::{CardView, get_card_view},
+    error::{Error, Result},
+    locale::Locale,
+    query::{CardView, get_card_view, get_owned_entity},
     sdl::AssetManager,
     ui::{Layout, Theme, UI},
 };
 use sdl2::{
+    pixels::Color,
     rect::Rect,
     render::{Canvas, Texture},
     video::Window,
@@ -17,21 +23,24 @@ pub struct RenderCtx<'a, 'b> {
     pub asset_manager: &'a mut AssetManager<'b>,
     pub canvas: &'a mut Canvas<Window>,
     pub ui: &'a UI,
+    pub locale: &'a Locale,
 }
 
 // ============================ Render Functions ===============================
 
-pub fn render_board(ctx: &mut RenderCtx) -> Result<(), String> {
+pub fn render_board(ctx: &mut RenderCtx) -> Result<()> {
     let (sprite, texture) = ctx
         .asset_manager
         .get_sprexture("cell")
-        .ok_or("ERR: Missing asset")?;
+        .ok_or_else(|| Error::MissingAsset("sprite 'cell'".to_string()))?;
 
     let Theme { fg, .. } = ctx.ui.palette.mono;
     texture.set_color_mod(fg.r, fg.g, fg.b);
 
     for rect in ctx.ui.layout.board {
-        ctx.canvas.copy(texture, sprite.region, rect)?;
+        ctx.canvas
+            .copy(texture, sprite.region, rect)
+            .map_err(Error::Sdl)?;
     }
 
     texture.set_color_mod(255, 255, 255);
@@ -39,6 +48,63 @@ pub fn render_board(ctx: &mut RenderCtx) -> Result<(), String> {
     Ok(())
 }
 
+/// Outlines the hinted hand card while selecting, and the hinted cell while placing that card.
+pub fn render_hint(
+    ctx: &mut RenderCtx,
+    hint: Move,
+    state: &battle::State,
+    active_entity: Option<Entity>,
+    components: &Components,
+    card_db: &CardDb,
+) -> Result<()> {
+    let &battle::State::Turn { phase, player } = state else {
+        return Ok(());
+    };
+
+    let rect = match phase {
+        TurnPhase::SelectCard { .. } => {
+            let Some(entity) = get_owned_entity(
+                player,
+                Position::Hand(hint.hand),
+                &components.owner,
+                &components.position,
+            ) else {
+                return Ok(());
+            };
+            let Some(card_view) = get_card_view(entity, components, card_db) else {
+                return Ok(());
+            };
+            get_dest_rect(active_entity, &card_view, &ctx.ui.layout)
+        }
+
+        TurnPhase::PlaceCard { entity, .. }
+            if components.position[entity] == Some(Position::Hand(hint.hand)) =>
+        {
+            ctx.ui.layout.board[hint.cell.index()]
+        }
+
+        _ => return Ok(()),
+    };
+
+    let color = ctx.ui.palette.mono.hint;
+    render_outline(ctx, rect, 2, color)?;
+    render_outline(ctx, rect, 3, color)
+}
+
+/// Draws `rect` grown by `grow` pixels on every side.
+pub fn render_outline(ctx: &mut RenderCtx, rect: Rect, grow: i32, color: Color) -> Result<()> {
+    let mut outline = rect.left_shifted(grow).top_shifted(grow);
+    outline.resize(
+        rect.width() + 2 * grow as u32,
+        rect.height() + 2 * grow as u32,
+    );
+
+    ctx.canvas.set_draw_color(color);
+    ctx.canvas.draw_rect(outline).map_err(Error::Sdl)?;
+
+    Ok(())
+}
+
 // vvv TODO vvv
 
 #[rustfmt::skip]
@@ -46,18 +112,23 @@ pub fn render_card(
     ctx: &mut RenderCtx,
     entity: Entity,
     active_entity: Option<Entity>,
+    hidden: bool,
     components: &Components,
     card_db: &CardDb
-) -> Result<(), String> {
+) -> Result<()> {
 
     let Some(card_view) = get_card_view(entity, components, card_db) else {
-        return Err(format!("No such entity: '{entity:?}'"));
+        return Err(Error::UnknownEntity(entity));
     };
 
     let dst = get_dest_rect(active_entity, &card_view, &ctx.ui.layout);
     let (texture, src) = get_texture(&card_view, ctx.asset_manager)?;
 
-    ctx.canvas.copy(texture, src, dst)?;
+    ctx.canvas.copy(texture, src, dst).map_err(Error::Sdl)?;
+
+    if hidden {
+        return Ok(());
+    }
 
     //
     // >>> TODO render stats <<<
@@ -90,7 +161,7 @@ pub fn render_card(
 }
 
 /// Returns card's destination region, extracting it from `Layout`.
-fn get_dest_rect(active_entity: Option<Entity>, card_view: &CardView, layout: &Layout) -> Rect {
+pub fn get_dest_rect(active_entity: Option<Entity>, card_view: &CardView, layout: &Layout) -> Rect {
     let Layout { board, hand, .. } = layout;
     let &CardView {
         entity,
@@ -120,21 +191,21 @@ fn get_dest_rect(active_entity: Option<Entity>, card_view: &CardView, layout: &L
 fn get_texture<'a>(
     card_view: &CardView,
     asset_manager: &'a AssetManager,
-) -> Result<(&'a Texture<'a>, Rect), String> {
+) -> Result<(&'a Texture<'a>, Rect)> {
     let sprites = asset_manager
         .card_sprites
         .get(card_view.id)
-        .ok_or(format!("No sprite for card id: '{}'", card_view.id))?;
+        .ok_or_else(|| Error::MissingAsset(format!("sprite of card {}", card_view.id)))?;
 
     let sprite = sprites[*card_view.owner as usize];
     let texture = asset_manager
         .get_texture(sprite.texture_id)
-        .ok_or(format!("No texture with id: '{}'", sprite.texture_id))?;
+        .ok_or_else(|| Error::MissingAsset(format!("texture {}", sprite.texture_id)))?;
 
     Ok((texture, sprite.region))
 }
 
-fn stat_char(value: u8) -> char {
+pub fn stat_char(value: u8) -> char {
     match value {
         x @ 0..=9 => (x + 48) as char,
         10 => 'A',
@@ -151,41 +222,61 @@ pub enum CharMode {
     BoldDark,
 }
 
-pub fn render_char(c: char, mode: CharMode, dst: Rect, ctx: &mut RenderCtx) -> Result<(), String> {
-    let Some((font, texture)) = ctx.asset_manager.get_font() else {
-        eprintln!("ERR: font not loaded");
-        return Ok(());
-    };
-    let Some(glyph) = font.glyphs[c as usize] else {
-        eprintln!("ERR: no such glyph");
-        return Ok(());
-    };
+/// Renders `text` centered in `area`. Whitespace and characters the font has no glyph for are left
+/// blank.
+pub fn render_text(text: &str, mode: CharMode, area: Rect, ctx: &mut RenderCtx) -> Result<()> {
+    let width = AssetManager::GLYPH_ADVANCE * text.chars().count() as i32;
+    let x = area.x() + (area.width() as i32 - width) / 2;
+
+    for (j, c) in text.chars().enumerate() {
+        if c.is_whitespace() || !ctx.asset_manager.get_font()?.0.has_glyph(c) {
+            continue;
+        }
+
+        let dst = Rect::new(
+            x + AssetManager::GLYPH_ADVANCE * j as i32,
+            area.y(),
+            AssetManager::GLYPH_WIDTH as u32,
+            AssetManager::GLYPH_HEIGHT as u32,
+        );
+        render_char(c, mode, dst, ctx)?;
+    }
+
+    Ok(())
+}
+
+pub fn render_char(c: char, mode: CharMode, dst: Rect, ctx: &mut RenderCtx) -> Result<()> {
+    let (font, texture) = ctx.asset_manager.get_font()?;
+    let &glyph = font
+        .glyphs
+        .get(&c)
+        .ok_or_else(|| Error::MissingAsset(format!("glyph '{c}'")))?;
 
-    let Theme { bg, fg } = ctx.ui.palette.mono;
+    let Theme { bg, fg, .. } = ctx.ui.palette.mono;
 
     match mode {
         CharMode::RegularLight => {
             texture.set_color_mod(fg.r, fg.g, fg.b);
-            ctx.canvas.copy(texture, glyph.0, dst)?;
+            ctx.canvas.copy(texture, glyph.0, dst).map_err(Error::Sdl)?;
             texture.set_color_mod(255, 255, 255);
         }
         CharMode::RegularDark => {
             texture.set_color_mod(bg.r, bg.g, bg.b);
-            ctx.canvas.copy(texture, glyph.0, dst)?;
+            ctx.canvas.copy(texture, glyph.0, dst).map_err(Error::Sdl)?;
             texture.set_color_mod(255, 255, 255);
         }
         CharMode::BoldLight => {
             texture.set_color_mod(bg.r, bg.g, bg.b);
-            ctx.canvas.copy(texture, glyph.1, dst)?;
+            ctx.canvas.copy(texture, glyph.1, dst).map_err(Error::Sdl)?;
             texture.set_color_mod(fg.r, fg.g, fg.b);
-            ctx.canvas.copy(texture, glyph.0, dst)?;
+            ctx.canvas.copy(texture, glyph.0, dst).map_err(Error::Sdl)?;
             texture.set_color_mod(255, 255, 255);
         }
         CharMode::BoldDark => {
             texture.set_color_mod(fg.r, fg.g, fg.b);
-            ctx.canvas.copy(texture, glyph.1, dst)?;
+            ctx.canvas.copy(texture, glyph.1, dst).map_err(Error::Sdl)?;
             texture.set_color_mod(bg.r, bg.g, bg.b);
-            ctx.canvas.copy(texture, glyph.0, dst)?;
+            ctx.canvas.copy(texture, glyph.0, dst).map_err(Error::Sdl)?;
             texture.set_color_mod(255, 255, 255);
         }
     }
@@ -1,4 +1,9 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
 
 use sdl2::{
     EventPump,
@@ -9,7 +14,12 @@ use sdl2::{
     video::{Window, WindowContext},
 };
 
-use crate::{core::battle::Player, data::Element, ui::Theme};
+use crate::{
+    core::battle::Player,
+    data::Element,
+    error::{Context, Error, Result},
+    ui::Theme,
+};
 
 // =============================== SdlSystems ==================================
 
@@ -21,29 +31,28 @@ pub struct SdlSystems {
 }
 
 impl SdlSystems {
-    const TITLE: &str = "Triple Triad";
     const WIDTH: u32 = 800;
     const HEIGHT: u32 = 600;
 
-    pub fn init() -> Result<Self, String> {
-        let sdl = sdl2::init()?;
-        let video = sdl.video()?;
+    pub fn init(title: &str) -> Result<Self> {
+        let sdl = sdl2::init().map_err(Error::Sdl)?;
+        let video = sdl.video().map_err(Error::Sdl)?;
 
         let window = video
-            .window(Self::TITLE, Self::WIDTH, Self::HEIGHT)
+            .window(title, Self::WIDTH, Self::HEIGHT)
             .position_centered()
             .build()
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| Error::Sdl(e.to_string()))?;
 
         let canvas = window
             .into_canvas()
             .present_vsync()
             .build()
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| Error::Sdl(e.to_string()))?;
 
         let texture_creator = canvas.texture_creator();
 
-        let event_pump = sdl.event_pump()?;
+        let event_pump = sdl.event_pump().map_err(Error::Sdl)?;
 
         Ok(SdlSystems {
             canvas,
@@ -64,17 +73,15 @@ pub struct AssetManager<'a> {
 }
 
 impl<'a> AssetManager<'a> {
-    // Font texture layout, TODO move to file
+    // Font texture layout, the characters of each row are listed in the layout file
     pub const GLYPH_HEIGHT: i32 = 22;
     pub const GLYPH_WIDTH: i32 = 18;
+    // glyphs are padded for their outline, text sets them closer
+    pub const GLYPH_ADVANCE: i32 = 12;
 
     const GLYPH_FG_ORIGIN: (i32, i32) = (0, 22);
-    const GLYPH_BG_ORIGIN: (i32, i32) = (0, 66);
-
-    const GLYPHS_PER_ROW: i32 = 6;
 
-    const GLYPH_FG_A: (i32, i32) = (72, 44);
-    const GLYPH_BG_A: (i32, i32) = (72, 88);
+    pub const GLYPHS_PER_ROW: usize = 6;
 
     pub const CARD_WIDTH: u32 = 128;
     pub const CARD_HEIGHT: u32 = 128;
@@ -86,8 +93,8 @@ impl<'a> AssetManager<'a> {
         texture_creator: &'a TextureCreator<WindowContext>,
         player: Player,
         cfg: BakeCardCfg,
-    ) -> Result<usize, String> {
-        let Theme { bg, fg } = cfg.theme;
+    ) -> Result<usize> {
+        let Theme { bg, fg, .. } = cfg.theme;
 
         let mut texture = texture_creator
             .create_texture(
@@ -96,12 +103,14 @@ impl<'a> AssetManager<'a> {
                 Self::CARD_WIDTH,
                 Self::CARD_HEIGHT,
             )
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| Error::Sdl(e.to_string()))?;
 
         // Tell SDL to respect texture's alpha channel when rendering it.
         // Without this, alpha will be ignored.
         texture.set_blend_mode(sdl2::render::BlendMode::Blend);
 
+        let mut result = Ok(());
+
         #[rustfmt::skip]
         canvas
             .with_texture_canvas(&mut texture, |texture_canvas| {
@@ -122,18 +131,25 @@ impl<'a> AssetManager<'a> {
                     }},
                 ];
 
+                // the closure can't fail, the first error is kept for after drawing
                 for CardParts { color, sprite_id_fn, } in card_parts {
                     let sprite_id = sprite_id_fn(player);
-                    // FIXME unwraps
-                    let sprite = self.get_sprite(sprite_id).unwrap();
-                    let texture = self.get_texture_mut(sprite.texture_id).unwrap();
+                    let Some((sprite, texture)) = self.get_sprexture(sprite_id) else {
+                        result = Err(Error::MissingAsset(format!("sprite '{sprite_id}'")));
+                        return;
+                    };
                     texture.set_color_mod(color.r, color.g, color.b);
-                    // FIXME error logging
-                    let _ = texture_canvas.copy(texture, sprite.region, None);
+                    let copied = texture_canvas.copy(texture, sprite.region, None);
                     texture.set_color_mod(255, 255, 255);
+
+                    if let Err(e) = copied {
+                        result = Err(Error::Sdl(e));
+                        return;
+                    }
                 }
             })
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| Error::Sdl(e.to_string()))?;
+        result?;
 
         let texture_id = self.textures.len();
         self.textures.push(texture);
@@ -146,13 +162,13 @@ impl<'a> AssetManager<'a> {
             .insert(name.to_string(), Sprite { region, texture_id });
     }
 
-    pub fn get_font(&mut self) -> Option<(&Font, &mut Texture<'a>)> {
-        let Some(texture) = self.textures.get_mut(self.font.texture_id) else {
-            eprintln!("ERR: missing font texture");
-            return None;
-        };
+    pub fn get_font(&mut self) -> Result<(&Font, &mut Texture<'a>)> {
+        let texture = self
+            .textures
+            .get_mut(self.font.texture_id)
+            .ok_or_else(|| Error::MissingAsset("font texture".to_string()))?;
 
-        Some((&self.font, texture))
+        Ok((&self.font, texture))
     }
 
     pub fn get_sprexture(&mut self, name: &str) -> Option<(Sprite, &mut Texture<'a>)> {
@@ -175,38 +191,41 @@ impl<'a> AssetManager<'a> {
     }
 
     #[rustfmt::skip]
+    /// Loads the font texture at `path`, whose glyphs are laid out as listed in `layout_path`, see
+    /// `Font::load_layout`.
     pub fn load_font(
         &mut self,
         texture_creator: &'a TextureCreator<WindowContext>,
         path: &str,
-    ) -> Result<(), String> {
-        let texture = texture_creator.load_texture(path)?;
-        let mut glyphs = vec![None; 256];
-
-        for j in 0i32..=9 {
-            let glyph_idx = '0' as usize + j as usize;
-
-            let x_incr = (Self::GLYPH_WIDTH * j) % (Self::GLYPH_WIDTH * Self::GLYPHS_PER_ROW);
-            let y_incr = Self::GLYPH_FG_ORIGIN.1 * (j / Self::GLYPHS_PER_ROW);
-
-            let fg_x = Self::GLYPH_FG_ORIGIN.0 + x_incr;
-            let fg_y = Self::GLYPH_FG_ORIGIN.1 + y_incr;
-
-            let bg_x = Self::GLYPH_BG_ORIGIN.0 + x_incr;
-            let bg_y = Self::GLYPH_BG_ORIGIN.1 + y_incr;
-
-            glyphs[glyph_idx] = Some((
-                Rect::new(fg_x, fg_y, Self::GLYPH_WIDTH as u32, Self::GLYPH_HEIGHT as u32),
-                Rect::new(bg_x, bg_y, Self::GLYPH_WIDTH as u32, Self::GLYPH_HEIGHT as u32),
-            ));
+        layout_path: &str,
+    ) -> Result<()> {
+        let texture = texture_creator
+            .load_texture(path)
+            .map_err(Error::Sdl)
+            .with_context(|| format!("loading font {path}"))?;
+        let rows =
+            Font::load_layout(layout_path).with_context(|| format!("loading {layout_path}"))?;
+        let mut glyphs = HashMap::new();
+
+        // the bold outlines follow the foreground rows
+        let bg_origin_y = Self::GLYPH_FG_ORIGIN.1 + Self::GLYPH_HEIGHT * rows.len() as i32;
+
+        for (row, chars) in rows.iter().enumerate() {
+            for (column, &c) in chars.iter().enumerate() {
+                let x = Self::GLYPH_FG_ORIGIN.0 + Self::GLYPH_WIDTH * column as i32;
+                let fg_y = Self::GLYPH_FG_ORIGIN.1 + Self::GLYPH_HEIGHT * row as i32;
+                let bg_y = bg_origin_y + Self::GLYPH_HEIGHT * row as i32;
+
+                glyphs.insert(
+                    c,
+                    (
+                        Rect::new(x, fg_y, Self::GLYPH_WIDTH as u32, Self::GLYPH_HEIGHT as u32),
+                        Rect::new(x, bg_y, Self::GLYPH_WIDTH as u32, Self::GLYPH_HEIGHT as u32),
+                    ),
+                );
+            }
         }
 
-        // NOTE 'A' is a one off for now. The complete bitmap font will be implemented in future
-        glyphs['A' as usize] = Some((
-            Rect::new(Self::GLYPH_FG_A.0, Self::GLYPH_FG_A.1, Self::GLYPH_WIDTH as u32, Self::GLYPH_HEIGHT as u32),
-            Rect::new(Self::GLYPH_BG_A.0, Self::GLYPH_BG_A.1, Self::GLYPH_WIDTH as u32, Self::GLYPH_HEIGHT as u32),
-        ));
-
         let texture_id = self.textures.len();
         let font = Font { glyphs, texture_id };
 
@@ -220,8 +239,12 @@ impl<'a> AssetManager<'a> {
         &mut self,
         texture_creator: &'a TextureCreator<WindowContext>,
         path: &str,
-    ) -> Result<usize, String> {
-        self.textures.push(texture_creator.load_texture(path)?);
+    ) -> Result<usize> {
+        let texture = texture_creator
+            .load_texture(path)
+            .map_err(Error::Sdl)
+            .with_context(|| format!("loading texture {path}"))?;
+        self.textures.push(texture);
 
         Ok(self.textures.len() - 1)
     }
@@ -241,7 +264,43 @@ pub struct Sprite {
 
 #[derive(Default)]
 pub struct Font {
-    // (fg, bg) glyphs for regular and bold font. Index is ASCII char value.
-    pub glyphs: Vec<Option<(Rect, Rect)>>,
+    // (fg, bg) glyphs for regular and bold font
+    pub glyphs: HashMap<char, (Rect, Rect)>,
     pub texture_id: usize,
 }
+
+impl Font {
+    /// Reads the characters of each glyph row of a font texture, one row per line of at most
+    /// `AssetManager::GLYPHS_PER_ROW` characters. Blank lines and lines starting with `#` are
+    /// skipped.
+    pub fn load_layout(path: impl AsRef<Path>) -> io::Result<Vec<Vec<char>>> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut rows = Vec::new();
+
+        for (j, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let row: Vec<char> = line.chars().collect();
+            if row.len() > AssetManager::GLYPHS_PER_ROW {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "line {}: more than {} glyphs",
+                        j + 1,
+                        AssetManager::GLYPHS_PER_ROW
+                    ),
+                ));
+            }
+            rows.push(row);
+        }
+
+        Ok(rows)
+    }
+
+    pub fn has_glyph(&self, c: char) -> bool {
+        self.glyphs.contains_key(&c)
+    }
+}
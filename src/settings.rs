@@ -0,0 +1,54 @@
+use std::fmt;
+
+use crate::locale::DEFAULT_LOCALE;
+
+// ========================================= Settings ==============================================
+
+/// Player preferences, saved with the `Profile` as `key value` pairs. Missing keys keep their
+/// default.
+#[derive(Clone, Debug)]
+pub struct Settings {
+    /// Code of the locale pack the game is shown in, e.g. `fr`.
+    pub locale: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            locale: DEFAULT_LOCALE.to_string(),
+        }
+    }
+}
+
+impl Settings {
+    /// Sets `key` to `value`, from its text form.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), SettingsError> {
+        match key {
+            "locale" if !value.is_empty() => self.locale = value.to_string(),
+            "locale" => return Err(SettingsError::InvalidValue(key.to_string())),
+            _ => return Err(SettingsError::UnknownKey(key.to_string())),
+        }
+
+        Ok(())
+    }
+
+    /// Returns every setting as `(key, value)`, in the text form read by `set`.
+    pub fn entries(&self) -> Vec<(&'static str, String)> {
+        vec![("locale", self.locale.clone())]
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum SettingsError {
+    InvalidValue(String),
+    UnknownKey(String),
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsError::InvalidValue(key) => write!(f, "invalid value for setting '{key}'"),
+            SettingsError::UnknownKey(key) => write!(f, "unknown setting '{key}'"),
+        }
+    }
+}
@@ -61,7 +61,7 @@ impl Rng {
         }
     }
 
-    fn u64(&mut self) -> u64 {
+    pub fn u64(&mut self) -> u64 {
         // xoshiro256++ generator - credits to Sebastiano Vigna (vigna@acm.org)
         const R: u32 = 23;
         const A: u32 = 17;
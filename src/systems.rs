@@ -1,21 +1,37 @@
 use crate::{
-    core::battle::{
-        self, BattleResult, BoardCoords, ComponentArray, Components, Direction, Entity, Player,
-        Position, TurnPhase,
+    controller::{Controller, Seats},
+    core::{
+        ai::{Agent, Strength, Weights},
+        analysis::Review,
+        battle::{
+            self, BattleResult, BoardCoords, ComponentArray, Components, Direction, Entity, Player,
+            Position, TurnPhase,
+        },
+        game::{Game, Move},
+        inference::HandInference,
+        record::MatchRecord,
+        rules::{RuleSet, captures},
+        stats::MatchStats,
     },
-    data::{CardDb, Stats},
+    data::CardDb,
+    error::{Error, Result},
     event::{Command, GameEvent},
-    query::{get_card_view, get_owned_entity, get_placed_entity, hand_size},
-    render::{RenderCtx, render_board, render_card},
+    query::{get_card_view, get_game, get_grid, get_owned_entity, get_placed_entity, hand_size},
+    render::{
+        CharMode, RenderCtx, get_dest_rect, render_board, render_card, render_char, render_hint,
+        render_outline, render_text, stat_char,
+    },
     rules::{wrap_decr, wrap_incr},
-    ui::Theme,
+    sdl::AssetManager,
+    sys::rand::Rng,
+    ui::{Inference, Theme},
 };
 use sdl2::{
     EventPump,
     keyboard::{Keycode, Mod},
     rect::Rect,
 };
-use std::collections::VecDeque;
+use std::{collections::VecDeque, sync::Arc, time::Duration};
 
 pub fn input_system(commands: &mut VecDeque<Command>, event_pump: &mut EventPump) {
     use sdl2::event::Event;
@@ -59,6 +75,16 @@ pub fn input_system(commands: &mut VecDeque<Command>, event_pump: &mut EventPump
                 ..
             } => Some(Command::Confirm),
 
+            Event::KeyDown {
+                keycode: Some(Keycode::Tab),
+                ..
+            } => Some(Command::Hint),
+
+            Event::KeyDown {
+                keycode: Some(Keycode::I),
+                ..
+            } => Some(Command::Inference),
+
             _ => None,
         } {
             commands.push_back(command);
@@ -66,6 +92,266 @@ pub fn input_system(commands: &mut VecDeque<Command>, event_pump: &mut EventPump
     }
 }
 
+/// Routes the commands of the seat on turn: the keyboard for a human, generated, received or
+/// scripted commands otherwise. Quitting and the inference panel stay on the keyboard, and a
+/// remote player disconnecting quits.
+///
+/// Outside of card selection and placement, the keyboard commands pass through.
+pub fn controller_system(
+    commands: &mut VecDeque<Command>,
+    state: &battle::State,
+    components: &Components,
+    rules: &RuleSet,
+    seats: &mut Seats,
+    rng: &mut Rng,
+) {
+    let &battle::State::Turn {
+        phase: TurnPhase::SelectCard { .. } | TurnPhase::PlaceCard { .. },
+        player,
+    } = state
+    else {
+        return;
+    };
+
+    let keyboard: Vec<Command> = commands.drain(..).collect();
+    commands.extend(
+        keyboard
+            .iter()
+            .filter(|cmd| matches!(cmd, Command::Quit | Command::Inference)),
+    );
+
+    let is_move = |cmd: &Command| {
+        matches!(
+            cmd,
+            Command::Cancel | Command::Confirm | Command::MoveCursor(_)
+        )
+    };
+
+    match seats.get_mut(player) {
+        Controller::Human => {
+            commands.extend(keyboard.iter().filter(|cmd| matches!(cmd, Command::Hint)));
+
+            let moves: Vec<Command> = keyboard.into_iter().filter(is_move).collect();
+            if let Controller::Network(peer) = seats.get_mut(!player) {
+                for &cmd in &moves {
+                    peer.send(cmd);
+                }
+            }
+            commands.extend(moves);
+        }
+
+        Controller::Ai(agent) => ai_system(commands, state, components, rules, agent, rng),
+
+        Controller::Network(peer) => match peer.recv() {
+            Some(cmd) if is_move(&cmd) => commands.push_back(cmd),
+            // the match can't go on without the remote player
+            None if !peer.is_connected() => commands.push_back(Command::Quit),
+            _ => {}
+        },
+
+        Controller::Script(script) => {
+            if let Some(cmd) = script.next_command().filter(is_move) {
+                commands.push_back(cmd);
+            }
+        }
+    }
+}
+
+/// Plays the turns of the AI controlled seat by issuing the same commands a human would.
+///
+/// Moves are computed in the background, the seat simply waits for them.
+pub fn ai_system(
+    commands: &mut VecDeque<Command>,
+    state: &battle::State,
+    components: &Components,
+    rules: &RuleSet,
+    agent: &mut Agent,
+    rng: &mut Rng,
+) {
+    let &battle::State::Turn { phase, player } = state else {
+        return;
+    };
+
+    if player != agent.player
+        || !matches!(
+            phase,
+            TurnPhase::SelectCard { .. } | TurnPhase::PlaceCard { .. }
+        )
+    {
+        return;
+    }
+
+    let plan = match agent.plan {
+        Some(mv) => mv,
+        None => match agent.poll() {
+            Some(Some(mv)) => *agent.plan.insert(mv),
+            Some(None) => {
+                eprintln!("AI found no legal move");
+                return;
+            }
+            None => {
+                if agent.thinking().is_none()
+                    && let Some(game) = get_game(state, components)
+                {
+                    agent.think(game, *rules, rng.u64());
+                }
+                return;
+            }
+        },
+    };
+
+    match phase {
+        TurnPhase::SelectCard { cursor, .. } => {
+            let maxlen = hand_size(player, &components.owner, &components.position);
+            let steps = (plan.hand + maxlen - cursor) % maxlen;
+
+            for _ in 0..steps {
+                commands.push_back(Command::MoveCursor(Direction::Down));
+            }
+            commands.push_back(Command::Confirm);
+        }
+
+        TurnPhase::PlaceCard { cursor, .. } => {
+            let (dx, dir_x) = step(cursor.x(), plan.cell.x(), Direction::Left, Direction::Right);
+            let (dy, dir_y) = step(cursor.y(), plan.cell.y(), Direction::Up, Direction::Down);
+
+            for _ in 0..dx {
+                commands.push_back(Command::MoveCursor(dir_x));
+            }
+            for _ in 0..dy {
+                commands.push_back(Command::MoveCursor(dir_y));
+            }
+            commands.push_back(Command::Confirm);
+
+            agent.plan = None;
+        }
+
+        _ => {}
+    }
+
+    fn step(from: usize, to: usize, back: Direction, forth: Direction) -> (usize, Direction) {
+        if to < from {
+            (from - to, back)
+        } else {
+            (to - from, forth)
+        }
+    }
+}
+
+/// Best move for the player on turn, searched in the background when asked for.
+pub struct Hint {
+    pub shown: Option<Move>,
+    rules: RuleSet,
+    /// Player whose hand is hidden, searched as one drawn from what is known of it.
+    concealed: Option<Player>,
+    /// Position the pending search is for, its reply is dropped if the match moved on.
+    asked: Option<Game>,
+    agent: Agent,
+}
+
+impl Hint {
+    pub fn new(rules: RuleSet, concealed: Option<Player>, card_db: Arc<CardDb>) -> Self {
+        Self {
+            shown: None,
+            rules,
+            concealed,
+            asked: None,
+            agent: Agent::new(Player::P1, Strength::PERFECT, Weights::default(), card_db),
+        }
+    }
+}
+
+/// Searches the position when the player on turn asks for a hint. The hint lasts until the turn
+/// ends.
+///
+/// A concealed hand is searched as one drawn from `inference`, so that the hint tells no more than
+/// the player knows. Without inference there is no hint.
+pub fn hint_system(
+    commands: &VecDeque<Command>,
+    game_events: &mut VecDeque<GameEvent>,
+    state: &battle::State,
+    components: &Components,
+    inference: Option<&HandInference>,
+    hint: &mut Hint,
+    rng: &mut Rng,
+) {
+    if let Some(asked) = hint.asked
+        && let Some(reply) = hint.agent.poll()
+    {
+        hint.asked = None;
+        if let Some(mv) = reply
+            && get_game(state, components) == Some(asked)
+        {
+            hint.shown = Some(mv);
+            game_events.push_back(GameEvent::HintShown);
+        }
+    }
+
+    let battle::State::Turn {
+        phase: TurnPhase::SelectCard { .. } | TurnPhase::PlaceCard { .. },
+        ..
+    } = state
+    else {
+        hint.shown = None;
+        return;
+    };
+
+    if hint.shown.is_some()
+        || hint.asked.is_some()
+        || !commands.iter().any(|cmd| matches!(cmd, Command::Hint))
+    {
+        return;
+    }
+
+    let Some(game) = get_game(state, components) else {
+        return;
+    };
+
+    let mut searched = game;
+    match (hint.concealed, inference) {
+        (Some(player), Some(inference)) => {
+            let mut sample = inference.sample(rng).into_iter();
+            for slot in &mut searched.hands[player as usize] {
+                *slot = slot.and_then(|_| sample.next());
+            }
+        }
+        (Some(_), None) => return,
+        (None, _) => {}
+    }
+
+    hint.agent.player = game.turn;
+    hint.agent.think(searched, hint.rules, rng.u64());
+    hint.asked = Some(game);
+}
+
+/// Tracks the concealed hand of `player` as its cards are played, and toggles the inference panel.
+pub fn inference_system(
+    commands: &VecDeque<Command>,
+    game_events: &VecDeque<GameEvent>,
+    state: &battle::State,
+    components: &Components,
+    player: Player,
+    inference: &mut HandInference,
+    shown: &mut bool,
+) {
+    if commands.iter().any(|cmd| matches!(cmd, Command::Inference)) {
+        *shown = !*shown;
+    }
+
+    if !matches!(state, battle::State::Turn { player: mover, .. } if *mover == player) {
+        return;
+    }
+
+    let grid = get_grid(components);
+    for event in game_events {
+        if let GameEvent::CardPlaced(mv) = event
+            && let Some(cell) = grid[mv.cell.index()]
+        {
+            inference.reveal(cell.card);
+        }
+    }
+}
+
 pub fn selection_system(
     commands: &VecDeque<Command>,
     game_events: &mut VecDeque<GameEvent>,
@@ -127,7 +413,7 @@ pub fn placement_system(
         return;
     };
 
-    let mut place_dst: Option<Position> = None;
+    let mut place_dst: Option<BoardCoords> = None;
     for command in commands.iter() {
         match command {
             Command::MoveCursor(Direction::Down) => *cursor = cursor.moved_down(),
@@ -142,10 +428,10 @@ pub fn placement_system(
                     eprintln!("Cursor out of bounds. {} {}", cursor.x(), cursor.y());
                     continue;
                 };
-                let position = Position::Board(board_coords);
                 // the destination cell is not occupied
-                if get_placed_entity(position, &components.position).is_none() {
-                    place_dst = Some(position);
+                if get_placed_entity(Position::Board(board_coords), &components.position).is_none()
+                {
+                    place_dst = Some(board_coords);
                 }
             }
 
@@ -157,12 +443,12 @@ pub fn placement_system(
     // replace position component
     // shift hand that has position > saved
     // fire event placed
-    if let Some(position) = place_dst {
+    if let Some(cell) = place_dst {
         let Some(Position::Hand(selected_hand_idx)) = components.position[*entity] else {
             return;
         };
 
-        components.position[*entity] = Some(position);
+        components.position[*entity] = Some(Position::Board(cell));
         let player = &components.owner[*entity];
 
         for e in Entity::iter() {
@@ -179,7 +465,10 @@ pub fn placement_system(
             }
         }
 
-        game_events.push_back(GameEvent::CardPlaced);
+        game_events.push_back(GameEvent::CardPlaced(Move {
+            hand: selected_hand_idx,
+            cell,
+        }));
     }
 }
 
@@ -187,6 +476,7 @@ pub fn rule_system(
     game_events: &mut VecDeque<Entity>,
     state: &battle::State,
     components: &Components,
+    rules: &RuleSet,
     card_db: &CardDb,
 ) {
     let battle::State::Turn {
@@ -197,61 +487,14 @@ pub fn rule_system(
         return;
     };
 
-    let Some(placed_card) = get_card_view(*entity, components, card_db) else {
+    let Some(Position::Board(board_coords)) = components.position[*entity] else {
         return;
     };
 
-    let &Position::Board(board_coords) = placed_card.position else {
-        return;
-    };
-
-    let &Stats { top, rgt, btm, lft } = placed_card.stats;
-
-    struct BattleCheck {
-        target: Option<BoardCoords>,
-        atk_stat: u8,
-        def_stat_fn: fn(&Stats) -> u8,
-    }
-
-    let checks = [
-        BattleCheck {
-            target: board_coords.neighbor(Direction::Left),
-            atk_stat: lft,
-            def_stat_fn: |s| s.rgt,
-        },
-        BattleCheck {
-            target: board_coords.neighbor(Direction::Right),
-            atk_stat: rgt,
-            def_stat_fn: |s| s.lft,
-        },
-        BattleCheck {
-            target: board_coords.neighbor(Direction::Up),
-            atk_stat: top,
-            def_stat_fn: |s| s.btm,
-        },
-        BattleCheck {
-            target: board_coords.neighbor(Direction::Down),
-            atk_stat: btm,
-            def_stat_fn: |s| s.top,
-        },
-    ];
-
-    for check in checks {
-        if let Some(board_coords) = check.target {
-            let Some(neighbor_entity) =
-                get_placed_entity(Position::Board(board_coords), &components.position)
-            else {
-                continue;
-            };
-            let Some(neighbor_card) = get_card_view(neighbor_entity, components, card_db) else {
-                continue;
-            };
-            if placed_card.owner == neighbor_card.owner {
-                continue;
-            }
-            if check.atk_stat > (check.def_stat_fn)(neighbor_card.stats) {
-                game_events.push_back(neighbor_entity);
-            }
+    let grid = get_grid(components);
+    for coords in captures(&grid, board_coords, *rules, card_db).iter() {
+        if let Some(target) = get_placed_entity(Position::Board(coords), &components.position) {
+            game_events.push_back(target);
         }
     }
 }
@@ -312,13 +555,78 @@ pub fn win_system(
     }
 }
 
+pub fn stats_system(events: &VecDeque<GameEvent>, state: &battle::State, stats: &mut MatchStats) {
+    let &battle::State::Turn { player, .. } = state else {
+        return;
+    };
+
+    for event in events {
+        match event {
+            GameEvent::CardPlaced(_) => stats.record_placement(player),
+            GameEvent::CardFlipped => stats.record_capture(player),
+            GameEvent::HintShown => stats.record_hint(player),
+            _ => {}
+        }
+    }
+}
+
+pub fn record_system(events: &VecDeque<GameEvent>, record: &mut MatchRecord) {
+    for event in events {
+        if let GameEvent::CardPlaced(mv) = event {
+            record.moves.push(*mv);
+        }
+    }
+}
+
+/// Steps through the reviewed turns. Cancel leaves the review.
+pub fn review_system(commands: &VecDeque<Command>, review: &mut Option<Review>) {
+    let Some(current) = review.as_mut() else {
+        return;
+    };
+
+    for command in commands {
+        match command {
+            Command::MoveCursor(Direction::Left | Direction::Up) => current.prev(),
+            Command::MoveCursor(Direction::Right | Direction::Down) => current.next(),
+            Command::Cancel => {
+                *review = None;
+                return;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// What is drawn of the match besides the board and the cards.
+#[derive(Clone, Copy, Default)]
+pub struct Overlays<'a> {
+    /// Cards in the hand of this player are shown face down.
+    pub concealed: Option<Player>,
+    pub hint: Option<Move>,
+    /// Player thinking about a move, and for how long.
+    pub thinking: Option<(Player, Duration)>,
+    pub inference: Option<&'a HandInference>,
+    /// Shown once the match is over.
+    pub stats: Option<&'a MatchStats>,
+}
+
+/// Renders the match and its overlays.
 pub fn render_system(
     ctx: &mut RenderCtx,
     state: &battle::State,
     components: &Components,
     card_db: &CardDb,
-) -> Result<(), String> {
-    let Theme { bg, fg } = ctx.ui.palette.mono;
+    overlays: Overlays,
+) -> Result<()> {
+    let Overlays {
+        concealed,
+        hint,
+        thinking,
+        inference,
+        stats,
+    } = overlays;
+
+    let Theme { bg, fg, .. } = ctx.ui.palette.mono;
 
     ctx.canvas.set_draw_color(bg);
     ctx.canvas.clear();
@@ -344,8 +652,47 @@ pub fn render_system(
 
         _ => None,
     };
+    let is_hidden = |entity: Entity| {
+        concealed.is_some()
+            && components.owner[entity] == concealed
+            && matches!(components.position[entity], Some(Position::Hand(_)))
+    };
     for entity in Entity::iter() {
-        render_card(ctx, entity, active_entity, components, card_db)?;
+        render_card(
+            ctx,
+            entity,
+            active_entity,
+            is_hidden(entity),
+            components,
+            card_db,
+        )?;
+    }
+
+    if let Some(entity) = active_entity.filter(|&entity| !is_hidden(entity))
+        && let (Some(card), Some(owner)) = (components.card[entity], components.owner[entity])
+    {
+        let mode = match owner {
+            Player::P1 => CharMode::BoldLight,
+            Player::P2 => CharMode::BoldDark,
+        };
+        let name = ctx.locale.card_name(card);
+        render_text(name, mode, ctx.ui.layout.card_name, ctx)?;
+    }
+
+    if let Some(hint) = hint {
+        render_hint(ctx, hint, state, active_entity, components, card_db)?;
+    }
+
+    if let Some((player, elapsed)) = thinking {
+        render_thinking(ctx, player, elapsed)?;
+    }
+
+    if let Some(inference) = inference {
+        render_inference(ctx, inference, card_db)?;
+    }
+
+    if let (battle::State::End { .. }, Some(stats)) = (state, stats) {
+        render_match_stats(ctx, stats)?;
     }
 
     // render cursor
@@ -354,11 +701,10 @@ pub fn render_system(
             phase: TurnPhase::SelectCard { cursor, .. },
             player: Player::P1,
         } => {
-            let s_cursor = ctx.asset_manager.get_sprite("cursor").unwrap();
-            let t_cursor = ctx
+            let (s_cursor, t_cursor) = ctx
                 .asset_manager
-                .get_texture_mut(s_cursor.texture_id)
-                .unwrap();
+                .get_sprexture("cursor")
+                .ok_or_else(|| Error::MissingAsset("sprite 'cursor'".to_string()))?;
             t_cursor.set_color_mod(fg.r, fg.g, fg.b);
 
             let card_rect = ctx.ui.layout.hand.p1[*cursor];
@@ -370,7 +716,9 @@ pub fn render_system(
                 s_cursor.region.height(),
             );
 
-            ctx.canvas.copy(t_cursor, s_cursor.region, cursor_rect)?;
+            ctx.canvas
+                .copy(t_cursor, s_cursor.region, cursor_rect)
+                .map_err(Error::Sdl)?;
 
             t_cursor.set_color_mod(255, 255, 255);
         }
@@ -379,11 +727,10 @@ pub fn render_system(
             phase: TurnPhase::SelectCard { cursor, .. },
             player: Player::P2,
         } => {
-            let s_cursor = ctx.asset_manager.get_sprite("cursor").unwrap();
-            let t_cursor = ctx
+            let (s_cursor, t_cursor) = ctx
                 .asset_manager
-                .get_texture_mut(s_cursor.texture_id)
-                .unwrap();
+                .get_sprexture("cursor")
+                .ok_or_else(|| Error::MissingAsset("sprite 'cursor'".to_string()))?;
             t_cursor.set_color_mod(fg.r, fg.g, fg.b);
 
             let card_rect = ctx.ui.layout.hand.p2[*cursor];
@@ -395,15 +742,17 @@ pub fn render_system(
                 s_cursor.region.height(),
             );
 
-            ctx.canvas.copy_ex(
-                t_cursor,
-                s_cursor.region,
-                cursor_rect,
-                0.0,
-                None,
-                true,
-                false,
-            )?;
+            ctx.canvas
+                .copy_ex(
+                    t_cursor,
+                    s_cursor.region,
+                    cursor_rect,
+                    0.0,
+                    None,
+                    true,
+                    false,
+                )
+                .map_err(Error::Sdl)?;
 
             t_cursor.set_color_mod(255, 255, 255);
         }
@@ -417,7 +766,7 @@ pub fn render_system(
             cursor.resize(card_rect.width() + 16, card_rect.height() + 16);
 
             ctx.canvas.set_draw_color(fg);
-            ctx.canvas.draw_rect(cursor)?;
+            ctx.canvas.draw_rect(cursor).map_err(Error::Sdl)?;
         }
 
         _ => {}
@@ -428,6 +777,120 @@ pub fn render_system(
     Ok(())
 }
 
+/// Draws dots filling up one at a time below `player`'s turn indicator.
+fn render_thinking(ctx: &mut RenderCtx, player: Player, elapsed: Duration) -> Result<()> {
+    const DOTS: u32 = 3;
+    const STEP: Duration = Duration::from_millis(300);
+
+    let area = match player {
+        Player::P1 => ctx.ui.layout.thinking.p1,
+        Player::P2 => ctx.ui.layout.thinking.p2,
+    };
+    let size = area.height();
+    let gap = (area.width() - DOTS * size) / (DOTS - 1);
+    let shown = (elapsed.as_millis() / STEP.as_millis()) as u32 % (DOTS + 1);
+
+    ctx.canvas.set_draw_color(ctx.ui.palette.mono.fg);
+    for j in 0..shown {
+        let x = area.x() + (j * (size + gap)) as i32;
+        ctx.canvas
+            .fill_rect(Rect::new(x, area.y(), size, size))
+            .map_err(Error::Sdl)?;
+    }
+
+    Ok(())
+}
+
+/// Writes the counters of each player below their side of the board, in their color.
+fn render_match_stats(ctx: &mut RenderCtx, stats: &MatchStats) -> Result<()> {
+    for (player, area, mode) in [
+        (
+            Player::P1,
+            ctx.ui.layout.match_stats.p1,
+            CharMode::BoldLight,
+        ),
+        (Player::P2, ctx.ui.layout.match_stats.p2, CharMode::BoldDark),
+    ] {
+        let j = player as usize;
+        let text = ctx.locale.format(
+            "match-stats",
+            &[
+                ("placed", &stats.placed[j]),
+                ("captured", &stats.captured[j]),
+                ("hints", &stats.hints[j]),
+            ],
+        );
+        render_text(&text, mode, area, ctx)?;
+    }
+
+    Ok(())
+}
+
+/// Lists the candidate cards of a concealed hand by their sides, with a bar showing how likely
+/// each card is to be in the hand.
+fn render_inference(
+    ctx: &mut RenderCtx,
+    inference: &HandInference,
+    card_db: &CardDb,
+) -> Result<()> {
+    let Theme { bg, fg, .. } = ctx.ui.palette.mono;
+    let Inference {
+        panel,
+        columns,
+        row_height,
+        padding,
+        bar_height,
+    } = ctx.ui.layout.inference;
+
+    ctx.canvas.set_draw_color(bg);
+    ctx.canvas.fill_rect(panel).map_err(Error::Sdl)?;
+    render_outline(ctx, panel, 0, fg)?;
+
+    let rows = (panel.height() - 2 * padding) / row_height;
+    let column_width = panel.width() / columns;
+    let glyph = (
+        AssetManager::GLYPH_WIDTH as u32,
+        AssetManager::GLYPH_HEIGHT as u32,
+    );
+
+    for (j, (card, probability)) in inference
+        .distribution()
+        .into_iter()
+        .take((rows * columns) as usize)
+        .enumerate()
+    {
+        let (column, row) = (j as u32 / rows, j as u32 % rows);
+        let x = panel.x() + (column * column_width + padding) as i32;
+        let y = panel.y() + (padding + row * row_height) as i32;
+
+        let stats = &card_db.stats[card.index()];
+        for (k, side) in [stats.top, stats.rgt, stats.btm, stats.lft]
+            .into_iter()
+            .enumerate()
+        {
+            let dst = Rect::new(x + k as i32 * glyph.0 as i32, y, glyph.0, glyph.1);
+            render_char(stat_char(side), CharMode::RegularLight, dst, ctx)?;
+        }
+
+        let bar_x = x + (4 * glyph.0 + padding) as i32;
+        let bar_y = y + ((glyph.1 - bar_height) / 2) as i32;
+        let bar_width = column_width - 4 * glyph.0 - 3 * padding;
+        let filled = (bar_width as f64 * probability).round() as u32;
+
+        ctx.canvas.set_draw_color(fg);
+        ctx.canvas
+            .draw_rect(Rect::new(bar_x, bar_y, bar_width, bar_height))
+            .map_err(Error::Sdl)?;
+        if filled > 0 {
+            ctx.canvas
+                .fill_rect(Rect::new(bar_x, bar_y, filled, bar_height))
+                .map_err(Error::Sdl)?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Returns whether the game is running or not.
 pub fn director_system(
     events: &VecDeque<GameEvent>,
@@ -482,7 +945,7 @@ pub fn director_system(
             let deselected = events
                 .iter()
                 .any(|e| matches!(e, GameEvent::CardDeselected));
-            let placed = events.iter().any(|e| matches!(e, GameEvent::CardPlaced));
+            let placed = events.iter().any(|e| matches!(e, GameEvent::CardPlaced(_)));
 
             if deselected {
                 let cursor = position[*entity].map_or(0, |pos| match pos {
@@ -536,3 +999,75 @@ pub fn director_system(
         battle::State::End { .. } => *state,
     };
 }
+
+/// Renders the reviewed position: the chosen move is shown like the placement cursor, the solver's
+/// best move like a hint. The turn number is shown on top and the evaluation loss on the mover's
+/// turn indicator.
+pub fn render_review_system(ctx: &mut RenderCtx, review: &Review, card_db: &CardDb) -> Result<()> {
+    let Theme { bg, fg, hint } = ctx.ui.palette.mono;
+
+    ctx.canvas.set_draw_color(bg);
+    ctx.canvas.clear();
+
+    render_board(ctx)?;
+
+    let Some(game) = review.position() else {
+        ctx.canvas.present();
+        return Ok(());
+    };
+    let components = game.components();
+    let report = review.report();
+
+    let hand_entity = |hand: usize| {
+        get_owned_entity(
+            game.turn,
+            Position::Hand(hand),
+            &components.owner,
+            &components.position,
+        )
+    };
+
+    let active_entity = report.and_then(|report| hand_entity(report.chosen.hand));
+    for entity in Entity::iter() {
+        render_card(ctx, entity, active_entity, false, &components, card_db)?;
+    }
+
+    if let Some(report) = report {
+        let chosen_cell = ctx.ui.layout.board[report.chosen.cell.index()];
+        render_outline(ctx, chosen_cell, 8, fg)?;
+
+        let best_cell = ctx.ui.layout.board[report.best.cell.index()];
+        render_outline(ctx, best_cell, 2, hint)?;
+
+        let best_card = hand_entity(report.best.hand)
+            .and_then(|entity| get_card_view(entity, &components, card_db));
+        if let Some(card_view) = best_card {
+            let rect = get_dest_rect(active_entity, &card_view, &ctx.ui.layout);
+            render_outline(ctx, rect, 2, hint)?;
+        }
+
+        let indicator = match report.player {
+            Player::P1 => ctx.ui.layout.turn_indicator.p1,
+            Player::P2 => ctx.ui.layout.turn_indicator.p2,
+        };
+        let loss = stat_char(report.loss().clamp(0, 10) as u8);
+        let mode = if report.is_blunder() {
+            CharMode::BoldDark
+        } else {
+            CharMode::BoldLight
+        };
+        let dst = Rect::from_center(
+            indicator.center(),
+            AssetManager::GLYPH_WIDTH as u32,
+            AssetManager::GLYPH_HEIGHT as u32,
+        );
+        render_char(loss, mode, dst, ctx)?;
+    }
+
+    let turn = stat_char((review.turn + 1).min(10) as u8);
+    render_char(turn, CharMode::BoldLight, ctx.ui.layout.review.turn, ctx)?;
+
+    ctx.canvas.present();
+
+    Ok(())
+}
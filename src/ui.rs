@@ -18,6 +18,7 @@ pub struct Palette {
 pub struct Theme {
     pub bg: Color,
     pub fg: Color,
+    pub hint: Color,
 }
 
 impl Default for Theme {
@@ -25,6 +26,7 @@ impl Default for Theme {
         Theme {
             bg: Color::RGB(5, 15, 26),
             fg: Color::RGB(163, 184, 204),
+            hint: Color::RGB(230, 180, 80),
         }
     }
 }
@@ -52,7 +54,14 @@ impl Default for Wireframe {
 pub struct Layout {
     pub board: [Rect; 9],
     pub card: Card,
+    /// Name of the card being played, centered below the board.
+    pub card_name: Rect,
     pub hand: Hand,
+    pub inference: Inference,
+    /// Counters of each player at the end of a match, below their side of the board.
+    pub match_stats: MatchStats,
+    pub review: Review,
+    pub thinking: Thinking,
     pub turn_indicator: TurnIndicator,
 }
 
@@ -76,7 +85,12 @@ impl Default for Layout {
                 Rect::new(478, 378, 128, 128),
             ],
             card: Card::default(),
+            card_name: Rect::new(0, 554, 800, 22),
             hand: Hand::default(),
+            inference: Inference::default(),
+            match_stats: MatchStats::default(),
+            review: Review::default(),
+            thinking: Thinking::default(),
             turn_indicator: TurnIndicator::default(),
         }
     }
@@ -141,6 +155,69 @@ impl Default for Hand {
     }
 }
 
+/// Opponent hand inference panel, over the board. Candidates fill the columns top to bottom.
+pub struct Inference {
+    pub panel: Rect,
+    pub columns: u32,
+    pub row_height: u32,
+    pub padding: u32,
+    pub bar_height: u32,
+}
+
+impl Default for Inference {
+    fn default() -> Self {
+        Inference {
+            panel: Rect::new(180, 86, 440, 428),
+            columns: 2,
+            row_height: 38,
+            padding: 12,
+            bar_height: 8,
+        }
+    }
+}
+
+/// Post-game review overlay.
+pub struct Review {
+    pub turn: Rect,
+}
+
+impl Default for Review {
+    fn default() -> Self {
+        Review {
+            turn: Rect::new(391, 43, 18, 22),
+        }
+    }
+}
+
+pub struct MatchStats {
+    pub p1: Rect,
+    pub p2: Rect,
+}
+
+impl Default for MatchStats {
+    fn default() -> Self {
+        MatchStats {
+            p1: Rect::new(0, 554, 400, 22),
+            p2: Rect::new(400, 554, 400, 22),
+        }
+    }
+}
+
+/// Thinking dots below the turn indicators, as tall as a dot.
+pub struct Thinking {
+    pub p1: Rect,
+    pub p2: Rect,
+}
+
+impl Default for Thinking {
+    fn default() -> Self {
+        Thinking {
+            p1: Rect::new(80, 78, 38, 6),
+            p2: Rect::new(683, 78, 38, 6),
+        }
+    }
+}
+
 pub struct TurnIndicator {
     pub p1: Rect,
     pub p2: Rect,